@@ -3,18 +3,16 @@ extern crate rand;
 
 fn main() {
     use probability::prelude::*;
+    use rand::distributions::Distribution;
 
-    struct Source<T>(T);
-
-    impl<T: rand::RngCore> source::Source for Source<T> {
-        fn read_u64(&mut self) -> u64 {
-            self.0.next_u64()
-        }
-    }
-
-    let mut source = Source(rand::rngs::OsRng::new().unwrap());
+    let mut source = source::Rand(rand::rngs::OsRng::new().unwrap());
     let distribution = Uniform::new(0.0, 1.0);
     let sampler = Independent(&distribution, &mut source);
     let samples = sampler.take(10).collect::<Vec<_>>();
-    print!("{:?}", samples);
+    println!("{:?}", samples);
+
+    let mut rng = rand::thread_rng();
+    let distribution = Gaussian::new(0.0, 1.0);
+    let samples = distribution.sample_iter(&mut rng).take(10).collect::<Vec<_>>();
+    println!("{:?}", samples);
 }
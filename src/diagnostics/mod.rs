@@ -0,0 +1,401 @@
+//! Convergence diagnostics of Markov chains.
+//!
+//! The diagnostics follow Vehtari et al. (2021). They take either a single
+//! chain of draws or several chains of equal length. Where indicated, each
+//! chain is split into halves, so that the diagnostics also detect
+//! nonstationarity within the chains.
+
+use distribution::{Gaussian, Inverse};
+
+/// A collection of chains of draws.
+///
+/// The trait is implemented for a single chain given as a slice or vector and
+/// for several chains given as a slice or vector of vectors.
+pub trait Chains {
+    /// Return the chains.
+    fn chains(&self) -> Vec<&[f64]>;
+}
+
+impl Chains for [f64] {
+    #[inline]
+    fn chains(&self) -> Vec<&[f64]> {
+        vec![self]
+    }
+}
+
+impl Chains for Vec<f64> {
+    #[inline]
+    fn chains(&self) -> Vec<&[f64]> {
+        vec![self]
+    }
+}
+
+impl Chains for [Vec<f64>] {
+    #[inline]
+    fn chains(&self) -> Vec<&[f64]> {
+        self.iter().map(|chain| &chain[..]).collect()
+    }
+}
+
+impl Chains for Vec<Vec<f64>> {
+    #[inline]
+    fn chains(&self) -> Vec<&[f64]> {
+        self.iter().map(|chain| &chain[..]).collect()
+    }
+}
+
+/// Compute the autocorrelation function of a chain.
+///
+/// The autocorrelations are computed for all lags from zero up to the length
+/// of the chain minus one by the fast Fourier transform. The chain should not
+/// be constant.
+pub fn autocorrelation(x: &[f64]) -> Vec<f64> {
+    let mut rho = autocovariance(x);
+    let variance = rho[0];
+    for rho in &mut rho {
+        *rho /= variance;
+    }
+    rho
+}
+
+/// Compute the bulk effective sample size.
+///
+/// The effective sample size is computed for the rank-normalized split
+/// chains, which makes it robust to heavy tails and indicative of the
+/// efficiency of estimating the center of the distribution.
+pub fn bulk_effective_size<C>(chains: &C) -> f64
+where
+    C: Chains + ?Sized,
+{
+    compute_effective_size(&normalize(&split(&chains.chains())))
+}
+
+/// Compute the effective sample size of the mean.
+///
+/// The effective sample size is computed for the split chains using Geyer’s
+/// initial monotone sequence estimator of the autocorrelation time.
+pub fn effective_size<C>(chains: &C) -> f64
+where
+    C: Chains + ?Sized,
+{
+    compute_effective_size(&split(&chains.chains()))
+}
+
+/// Compute the split potential scale reduction factor, known as R-hat.
+///
+/// The value approaches one as the chains converge to a common distribution.
+/// Each chain should contain at least four draws.
+pub fn split_r_hat<C>(chains: &C) -> f64
+where
+    C: Chains + ?Sized,
+{
+    let chains = split(&chains.chains());
+    let (m, n) = (chains.len() as f64, chains[0].len() as f64);
+    let means = chains.iter().map(|chain| mean(chain)).collect::<Vec<_>>();
+    let within = chains.iter().map(|chain| variance(chain)).sum::<f64>() / m;
+    let between = variance(&means);
+    ((within * (n - 1.0) / n + between) / within).sqrt()
+}
+
+/// Compute the Monte Carlo standard error of the mean.
+///
+/// The standard deviation of all draws is divided by the square root of the
+/// effective sample size of the mean.
+pub fn standard_error<C>(chains: &C) -> f64
+where
+    C: Chains + ?Sized,
+{
+    let draws = chains.chains().concat();
+    (variance(&draws) / effective_size(chains)).sqrt()
+}
+
+/// Compute the tail effective sample size.
+///
+/// The effective sample size is computed for the indicators of the split
+/// chains falling below the 5% and 95% quantiles, and the smaller of the two
+/// is returned, which is indicative of the efficiency of estimating the tails
+/// of the distribution.
+pub fn tail_effective_size<C>(chains: &C) -> f64
+where
+    C: Chains + ?Sized,
+{
+    let chains = split(&chains.chains());
+    let mut draws = chains.concat();
+    draws.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    let quantile = |p: f64| draws[((p * draws.len() as f64) as usize).min(draws.len() - 1)];
+    let size = |q: f64| {
+        let chains = chains
+            .iter()
+            .map(|chain| {
+                chain
+                    .iter()
+                    .map(|&x| if x <= q { 1.0 } else { 0.0 })
+                    .collect()
+            })
+            .collect::<Vec<_>>();
+        compute_effective_size(&chains)
+    };
+    size(quantile(0.05)).min(size(quantile(0.95)))
+}
+
+fn autocovariance(x: &[f64]) -> Vec<f64> {
+    let n = x.len();
+    let size = (2 * n).next_power_of_two();
+    let mu = mean(x);
+    let mut re = x.iter().map(|&x| x - mu).collect::<Vec<_>>();
+    re.resize(size, 0.0);
+    let mut im = vec![0.0; size];
+    fft(&mut re, &mut im, false);
+    for (re, im) in re.iter_mut().zip(&mut im) {
+        *re = *re * *re + *im * *im;
+        *im = 0.0;
+    }
+    fft(&mut re, &mut im, true);
+    re.truncate(n);
+    for value in &mut re {
+        *value /= (size * n) as f64;
+    }
+    re
+}
+
+// Compute the effective sample size of the given chains following the
+// implementation in Stan.
+fn compute_effective_size(chains: &[Vec<f64>]) -> f64 {
+    let (m, n) = (chains.len(), chains[0].len());
+    should!(chains.iter().all(|chain| chain.len() == n));
+    if n < 4 {
+        return ::std::f64::NAN;
+    }
+    let covariances = chains
+        .iter()
+        .map(|chain| autocovariance(chain))
+        .collect::<Vec<_>>();
+    let means = chains.iter().map(|chain| mean(chain)).collect::<Vec<_>>();
+    let within =
+        covariances.iter().map(|c| c[0]).sum::<f64>() / m as f64 * n as f64 / (n - 1) as f64;
+    if within <= 0.0 {
+        return ::std::f64::NAN;
+    }
+    let mut plus = within * (n - 1) as f64 / n as f64;
+    if m > 1 {
+        plus += variance(&means);
+    }
+    let rho = |t: usize| {
+        let covariance = covariances.iter().map(|c| c[t]).sum::<f64>() / m as f64;
+        1.0 - (within - covariance) / plus
+    };
+    let mut rhos = vec![0.0; n];
+    rhos[0] = 1.0;
+    let (mut even, mut odd) = (1.0, rho(1));
+    rhos[1] = odd;
+    let mut t = 1;
+    while t + 5 < n && even + odd > 0.0 {
+        even = rho(t + 1);
+        odd = rho(t + 2);
+        if even + odd >= 0.0 {
+            rhos[t + 1] = even;
+            rhos[t + 2] = odd;
+        }
+        t += 2;
+    }
+    let last = t;
+    if even > 0.0 {
+        rhos[last + 1] = even;
+    }
+    let mut t = 1;
+    while t + 2 <= last {
+        if rhos[t + 1] + rhos[t + 2] > rhos[t - 1] + rhos[t] {
+            rhos[t + 1] = (rhos[t - 1] + rhos[t]) / 2.0;
+            rhos[t + 2] = rhos[t + 1];
+        }
+        t += 2;
+    }
+    let size = (m * n) as f64;
+    let tau = -1.0 + 2.0 * rhos[..last].iter().sum::<f64>() + rhos[last + 1];
+    size / tau.max(1.0 / size.log10())
+}
+
+fn fft(re: &mut [f64], im: &mut [f64], inverse: bool) {
+    let n = re.len();
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut length = 2;
+    while length <= n {
+        let angle = sign * 2.0 * ::std::f64::consts::PI / length as f64;
+        for start in (0..n).step_by(length) {
+            for k in 0..(length / 2) {
+                let (sin, cos) = (angle * k as f64).sin_cos();
+                let (a, b) = (start + k, start + k + length / 2);
+                let (x, y) = (re[b] * cos - im[b] * sin, re[b] * sin + im[b] * cos);
+                re[b] = re[a] - x;
+                im[b] = im[a] - y;
+                re[a] += x;
+                im[a] += y;
+            }
+        }
+        length <<= 1;
+    }
+}
+
+#[inline]
+fn mean(x: &[f64]) -> f64 {
+    x.iter().sum::<f64>() / x.len() as f64
+}
+
+// Replace the draws with the normal scores of their ranks among all draws.
+fn normalize(chains: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let draws = chains.concat();
+    let mut order = (0..draws.len()).collect::<Vec<_>>();
+    order.sort_by(|&i, &j| draws[i].partial_cmp(&draws[j]).unwrap());
+    let mut ranks = vec![0.0; draws.len()];
+    let mut i = 0;
+    while i < order.len() {
+        let mut j = i + 1;
+        while j < order.len() && draws[order[j]] == draws[order[i]] {
+            j += 1;
+        }
+        for &k in &order[i..j] {
+            ranks[k] = (i + j + 1) as f64 / 2.0;
+        }
+        i = j;
+    }
+    let gaussian = Gaussian::new(0.0, 1.0);
+    let size = draws.len() as f64;
+    ranks
+        .chunks(chains[0].len())
+        .map(|ranks| {
+            ranks
+                .iter()
+                .map(|&rank| gaussian.inverse((rank - 0.375) / (size + 0.25)))
+                .collect()
+        })
+        .collect()
+}
+
+// Split each chain into halves, dropping the middle draw of odd lengths.
+fn split(chains: &[&[f64]]) -> Vec<Vec<f64>> {
+    let mut halves = Vec::with_capacity(2 * chains.len());
+    for chain in chains {
+        let (n, m) = (chain.len(), chain.len() / 2);
+        halves.push(chain[..m].to_vec());
+        halves.push(chain[(n - m)..].to_vec());
+    }
+    halves
+}
+
+#[inline]
+fn variance(x: &[f64]) -> f64 {
+    let mu = mean(x);
+    x.iter().map(|&x| (x - mu) * (x - mu)).sum::<f64>() / (x.len() - 1) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use diagnostics;
+    use prelude::*;
+
+    fn autoregressive(phi: f64, n: usize, source: &mut source::Default) -> Vec<f64> {
+        let gaussian = Gaussian::new(0.0, (1.0 - phi * phi).sqrt());
+        let mut x = gaussian.sample(source) / (1.0 - phi * phi).sqrt();
+        (0..n)
+            .map(|_| {
+                x = phi * x + gaussian.sample(source);
+                x
+            })
+            .collect()
+    }
+
+    fn independent(n: usize, source: &mut source::Default) -> Vec<Vec<f64>> {
+        let d = Gaussian::new(0.0, 1.0);
+        (0..4)
+            .map(|_| Independent(&d, &mut *source).take(n).collect())
+            .collect()
+    }
+
+    #[test]
+    fn autocorrelation() {
+        let rho = diagnostics::autocorrelation(&[1.0, 2.0, 3.0, 4.0]);
+        assert::close(&rho[..], &[1.0, 0.25, -0.3, -0.45][..], 1e-14);
+
+        let mut source = source::default();
+        let x = autoregressive(0.5, 100000, &mut source);
+        let rho = diagnostics::autocorrelation(&x);
+        assert::close(&rho[..4], &[1.0, 0.5, 0.25, 0.125][..], 2e-2);
+    }
+
+    #[test]
+    fn bulk_effective_size() {
+        let mut source = source::default();
+        let chains = independent(1000, &mut source);
+        let size = diagnostics::bulk_effective_size(&chains);
+        assert!(3000.0 < size && size < 5000.0);
+    }
+
+    #[test]
+    fn effective_size() {
+        let mut source = source::default();
+        let chains = independent(1000, &mut source);
+        let size = diagnostics::effective_size(&chains);
+        assert!(3000.0 < size && size < 5000.0);
+
+        let phi = 0.9;
+        let chains = (0..4)
+            .map(|_| autoregressive(phi, 5000, &mut source))
+            .collect::<Vec<_>>();
+        let expected = 20000.0 * (1.0 - phi) / (1.0 + phi);
+        let size = diagnostics::effective_size(&chains);
+        assert::close(size / expected, 1.0, 0.3);
+        let size = diagnostics::effective_size(&chains[0]);
+        assert::close(size / expected * 4.0, 1.0, 0.3);
+    }
+
+    #[test]
+    fn split_r_hat() {
+        let mut source = source::default();
+        let mut chains = independent(1000, &mut source);
+        assert::close(diagnostics::split_r_hat(&chains), 1.0, 1e-2);
+        assert::close(diagnostics::split_r_hat(&chains[0]), 1.0, 2e-2);
+
+        for x in &mut chains[0] {
+            *x += 4.0;
+        }
+        assert!(diagnostics::split_r_hat(&chains) > 1.5);
+
+        let trend = (0..1000).map(|i| i as f64).collect::<Vec<_>>();
+        assert!(diagnostics::split_r_hat(&trend[..]) > 1.5);
+    }
+
+    #[test]
+    fn standard_error() {
+        let mut source = source::default();
+        let chains = independent(1000, &mut source);
+        let error = diagnostics::standard_error(&chains);
+        assert::close(error, 1.0 / 4000f64.sqrt(), 2e-3);
+    }
+
+    #[test]
+    fn tail_effective_size() {
+        let mut source = source::default();
+        let chains = independent(1000, &mut source);
+        let size = diagnostics::tail_effective_size(&chains);
+        assert!(2500.0 < size && size < 5500.0);
+
+        let chains = (0..4)
+            .map(|_| autoregressive(0.9, 1000, &mut source))
+            .collect::<Vec<_>>();
+        assert!(diagnostics::tail_effective_size(&chains) < 1000.0);
+    }
+}
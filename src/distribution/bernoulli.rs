@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// A Bernoulli distribution.
@@ -23,6 +23,14 @@ impl Bernoulli {
         }
     }
 
+    /// Create a Bernoulli distribution with success probability `p` or fail if
+    /// the parameter is invalid.
+    #[inline]
+    pub fn try_new(p: f64) -> Result<Self, Error> {
+        error::probability(p, "p")?;
+        Ok(Bernoulli::new(p))
+    }
+
     /// Create a Bernoulli distribution with failure probability `q`.
     ///
     /// It should hold that `q > 0` and `q < 1`. This constructor is preferable
@@ -37,6 +45,14 @@ impl Bernoulli {
         }
     }
 
+    /// Create a Bernoulli distribution with failure probability `q` or fail if
+    /// the parameter is invalid.
+    #[inline]
+    pub fn try_with_failure(q: f64) -> Result<Self, Error> {
+        error::probability(q, "q")?;
+        Ok(Bernoulli::with_failure(q))
+    }
+
     /// Return the success probability.
     #[inline(always)]
     pub fn p(&self) -> f64 {
@@ -61,6 +77,17 @@ impl distribution::Discrete for Bernoulli {
             0.0
         }
     }
+
+    #[inline]
+    fn ln_mass(&self, x: u8) -> f64 {
+        if x == 0 {
+            self.q.ln()
+        } else if x == 1 {
+            self.p.ln()
+        } else {
+            ::std::f64::NEG_INFINITY
+        }
+    }
 }
 
 impl distribution::Distribution for Bernoulli {
@@ -76,6 +103,39 @@ impl distribution::Distribution for Bernoulli {
             1.0
         }
     }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            ::std::f64::NEG_INFINITY
+        } else if x < 1.0 {
+            self.q.ln()
+        } else {
+            0.0
+        }
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            0.0
+        } else if x < 1.0 {
+            self.p.ln()
+        } else {
+            ::std::f64::NEG_INFINITY
+        }
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            1.0
+        } else if x < 1.0 {
+            self.p
+        } else {
+            0.0
+        }
+    }
 }
 
 impl distribution::Entropy for Bernoulli {
@@ -84,6 +144,27 @@ impl distribution::Entropy for Bernoulli {
     }
 }
 
+impl distribution::Fit for Bernoulli {
+    fn fit(data: &[u8]) -> Result<Estimate<Self>, FitError> {
+        if data.is_empty() {
+            return Err(FitError::Insufficient);
+        }
+        if data.iter().any(|&x| x > 1) {
+            return Err(FitError::OutOfSupport);
+        }
+        let p = fit::mean(data.iter().map(|&x| x as f64));
+        Ok(fit::discrete(Bernoulli::try_new(p)?, data, 0, true))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    ///
+    /// The estimate coincides with the maximum-likelihood one.
+    #[inline]
+    fn fit_moments(data: &[u8]) -> Result<Estimate<Self>, FitError> {
+        Self::fit(data)
+    }
+}
+
 impl distribution::Inverse for Bernoulli {
     #[inline]
     fn inverse(&self, p: f64) -> u8 {
@@ -94,6 +175,16 @@ impl distribution::Inverse for Bernoulli {
             1
         }
     }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> u8 {
+        should!(0.0 <= q && q <= 1.0);
+        if q >= self.p {
+            0
+        } else {
+            1
+        }
+    }
 }
 
 impl distribution::Kurtosis for Bernoulli {
@@ -193,6 +284,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fit() {
+        use distribution::{Error, FitError};
+
+        let estimate = Bernoulli::fit(&[0, 1, 1, 0, 1, 1, 1, 0]).unwrap();
+        assert_eq!(estimate.distribution.p(), 0.625);
+        assert::close(
+            estimate.ln_likelihood,
+            5.0 * 0.625f64.ln() + 3.0 * 0.375f64.ln(),
+            1e-14,
+        );
+
+        assert_eq!(Bernoulli::fit(&[0, 2]).unwrap_err(), FitError::OutOfSupport);
+        assert_eq!(
+            Bernoulli::fit(&[1, 1]).unwrap_err(),
+            FitError::Parameter(Error::NotProbability("p"))
+        );
+    }
+
+    #[test]
+    fn fit_moments() {
+        let estimate = Bernoulli::fit_moments(&[0, 1, 1, 0, 1, 1, 1, 0]).unwrap();
+        assert_eq!(estimate.distribution.p(), 0.625);
+    }
+
     #[test]
     fn inverse() {
         let d = new!(0.25);
@@ -201,11 +317,55 @@ mod tests {
         assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(0.25);
+        assert_eq!(d.inverse_survival(0.0), 1);
+        assert_eq!(d.inverse_survival(0.2), 1);
+        assert_eq!(d.inverse_survival(0.25), 0);
+        assert_eq!(d.inverse_survival(1.0), 0);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(0.5).kurtosis(), -2.0);
     }
 
+    #[test]
+    fn ln_distribution() {
+        use std::f64::NEG_INFINITY;
+
+        let d = new!(0.25);
+        assert_eq!(
+            &[-1.0, 0.0, 0.5, 1.0, 1.5]
+                .iter()
+                .map(|&x| d.ln_distribution(x))
+                .collect::<Vec<_>>(),
+            &[NEG_INFINITY, 0.75f64.ln(), 0.75f64.ln(), 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn ln_mass() {
+        use std::f64::NEG_INFINITY;
+
+        let d = new!(0.25);
+        assert_eq!(
+            &(0..3).map(|x| d.ln_mass(x)).collect::<Vec<_>>(),
+            &[0.75f64.ln(), 0.25f64.ln(), NEG_INFINITY]
+        );
+    }
+
+    #[test]
+    fn ln_survival() {
+        use std::f64::NEG_INFINITY;
+
+        let d = new!(failure 1e-20);
+        assert_eq!(d.ln_survival(-1.0), 0.0);
+        assert_eq!(d.ln_survival(0.5), (1.0 - 1e-20f64).ln());
+        assert_eq!(d.ln_survival(1.0), NEG_INFINITY);
+    }
+
     #[test]
     fn mass() {
         let d = new!(0.25);
@@ -249,6 +409,30 @@ mod tests {
         assert_eq!(new!(0.5).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(0.25);
+        let x = vec![-0.1, 0.0, 0.1, 0.25, 0.5, 1.0, 1.1];
+        let p = vec![1.0, 0.25, 0.25, 0.25, 0.25, 0.0, 0.0];
+        assert_eq!(&x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(), &p);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Bernoulli::try_new(0.25).is_ok());
+        assert!(Bernoulli::try_with_failure(0.25).is_ok());
+        assert_eq!(
+            Bernoulli::try_new(1.5).unwrap_err(),
+            Error::NotProbability("p")
+        );
+        assert_eq!(
+            Bernoulli::try_with_failure(0.0).unwrap_err(),
+            Error::NotProbability("q")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(0.25).variance(), 0.1875);
@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// A beta distribution.
@@ -29,6 +29,16 @@ impl Beta {
         }
     }
 
+    /// Create a beta distribution with shape parameters `alpha` and `beta`
+    /// on interval `[a, b]` or fail if the parameters are invalid.
+    #[inline]
+    pub fn try_new(alpha: f64, beta: f64, a: f64, b: f64) -> Result<Self, Error> {
+        error::positive(alpha, "alpha")?;
+        error::positive(beta, "beta")?;
+        error::less(a, b, ("a", "b"))?;
+        Ok(Beta::new(alpha, beta, a, b))
+    }
+
     /// Return the first shape parameter.
     #[inline(always)]
     pub fn alpha(&self) -> f64 {
@@ -65,6 +75,18 @@ impl distribution::Continuous for Beta {
                 / scale
         }
     }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        if x < self.a || x > self.b {
+            ::std::f64::NEG_INFINITY
+        } else {
+            let scale = self.b - self.a;
+            let x = (x - self.a) / scale;
+            (self.alpha - 1.0) * x.ln() + (self.beta - 1.0) * (-x).ln_1p()
+                - self.ln_beta
+                - scale.ln()
+        }
+    }
 }
 
 impl distribution::Distribution for Beta {
@@ -80,6 +102,27 @@ impl distribution::Distribution for Beta {
             ((x - self.a) / (self.b - self.a)).inc_beta(self.alpha, self.beta, self.ln_beta)
         }
     }
+
+    fn ln_distribution(&self, x: f64) -> f64 {
+        if x <= self.a {
+            ::std::f64::NEG_INFINITY
+        } else if x >= self.b {
+            0.0
+        } else {
+            self.distribution(x).ln()
+        }
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        use special::Beta;
+        if x <= self.a {
+            1.0
+        } else if x >= self.b {
+            0.0
+        } else {
+            ((self.b - x) / (self.b - self.a)).inc_beta(self.beta, self.alpha, self.ln_beta)
+        }
+    }
 }
 
 impl distribution::Entropy for Beta {
@@ -93,6 +136,91 @@ impl distribution::Entropy for Beta {
     }
 }
 
+impl distribution::Fit for Beta {
+    /// Estimate the parameters by the method of maximum likelihood.
+    ///
+    /// The distribution is fitted on the unit interval, and the likelihood
+    /// equations for the shape parameters are solved by the Newton method
+    /// starting from the method-of-moments estimate.
+    fn fit(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        use special::Gamma;
+        let start = Self::fit_moments(data)?.distribution;
+        let (mut alpha, mut beta) = (start.alpha, start.beta);
+        let g1 = fit::mean(data.iter().map(|x| x.ln()));
+        let g2 = fit::mean(data.iter().map(|x| (-x).ln_1p()));
+        let mut converged = false;
+        let mut iterations = 0;
+        while iterations < fit::ITERATIONS {
+            iterations += 1;
+            let digamma = (alpha + beta).digamma();
+            let trigamma = fit::trigamma(alpha + beta);
+            let f1 = alpha.digamma() - digamma - g1;
+            let f2 = beta.digamma() - digamma - g2;
+            let j11 = fit::trigamma(alpha) - trigamma;
+            let j22 = fit::trigamma(beta) - trigamma;
+            let determinant = j11 * j22 - trigamma * trigamma;
+            let mut delta_alpha = (f1 * j22 + f2 * trigamma) / determinant;
+            let mut delta_beta = (f2 * j11 + f1 * trigamma) / determinant;
+            if !delta_alpha.is_finite() || !delta_beta.is_finite() {
+                break;
+            }
+            if alpha - delta_alpha <= 0.0 {
+                delta_alpha = 0.5 * alpha;
+            }
+            if beta - delta_beta <= 0.0 {
+                delta_beta = 0.5 * beta;
+            }
+            alpha -= delta_alpha;
+            beta -= delta_beta;
+            if delta_alpha.abs() < fit::TOLERANCE * alpha
+                && delta_beta.abs() < fit::TOLERANCE * beta
+            {
+                converged = true;
+                break;
+            }
+        }
+        Ok(fit::continuous(
+            Beta::try_new(alpha, beta, 0.0, 1.0)?,
+            data,
+            iterations,
+            converged,
+        ))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    ///
+    /// The distribution is fitted on the unit interval.
+    fn fit_moments(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        fit::check(data, 2)?;
+        if data.iter().any(|&x| x <= 0.0 || x >= 1.0) {
+            return Err(FitError::OutOfSupport);
+        }
+        let mean = fit::mean(data.iter().cloned());
+        let variance = fit::variance(data.iter().cloned(), mean);
+        if variance == 0.0 {
+            return Err(FitError::Degenerate);
+        }
+        let c = mean * (1.0 - mean) / variance - 1.0;
+        Ok(fit::continuous(
+            Beta::try_new(mean * c, (1.0 - mean) * c, 0.0, 1.0)?,
+            data,
+            0,
+            true,
+        ))
+    }
+}
+
+impl distribution::Gradient for Beta {
+    #[inline]
+    fn gradient(&self, x: f64) -> f64 {
+        if x <= self.a || x >= self.b {
+            0.0
+        } else {
+            (self.alpha - 1.0) / (x - self.a) - (self.beta - 1.0) / (self.b - x)
+        }
+    }
+}
+
 impl distribution::Inverse for Beta {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
@@ -100,6 +228,13 @@ impl distribution::Inverse for Beta {
         should!(0.0 <= p && p <= 1.0);
         self.a + (self.b - self.a) * p.inv_inc_beta(self.alpha, self.beta, self.ln_beta)
     }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        use special::Beta;
+        should!(0.0 <= q && q <= 1.0);
+        self.b - (self.b - self.a) * q.inv_inc_beta(self.beta, self.alpha, self.ln_beta)
+    }
 }
 
 impl distribution::Kurtosis for Beta {
@@ -286,6 +421,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fit() {
+        use distribution::FitError;
+
+        let data = vec![0.12, 0.25, 0.31, 0.44, 0.52, 0.67, 0.71, 0.89];
+        let estimate = Beta::fit(&data).unwrap();
+        assert::close(estimate.distribution.alpha(), 1.7377504088243934e+00, 1e-12);
+        assert::close(estimate.distribution.beta(), 1.7970518958836945e+00, 1e-12);
+        assert::close(estimate.ln_likelihood, 7.1394774513089576e-01, 1e-12);
+        assert!(estimate.converged);
+        assert!(estimate.iterations > 0);
+
+        assert_eq!(Beta::fit(&[0.5, 1.0]).unwrap_err(), FitError::OutOfSupport);
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![0.12, 0.25, 0.31, 0.44, 0.52, 0.67, 0.71, 0.89];
+        let estimate = Beta::fit_moments(&data).unwrap();
+        assert::close(estimate.distribution.alpha(), 1.5851856077692572e+00, 1e-13);
+        assert::close(estimate.distribution.beta(), 1.6581609042906043e+00, 1e-13);
+        assert::close(estimate.ln_likelihood, 6.9498468620600284e-01, 1e-13);
+    }
+
+    #[test]
+    fn gradient() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        let x = vec![-1.5, -1.0, -0.5, 0.0, 1.0, 1.5, 2.0, 2.5];
+        let p = vec![0.0, 0.0, 1.2, 0.0, -1.5, -3.6, 0.0, 0.0];
+        assert::close(
+            &x.iter().map(|&x| d.gradient(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
     #[test]
     fn inverse() {
         let d = new!(1.0, 2.0, 3.0, 4.0);
@@ -323,6 +494,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        for &q in &[0.0, 0.001, 0.1, 0.5, 0.9, 0.999, 1.0] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-12);
+        }
+        let d = new!(2.0, 3.0, 0.0, 1.0);
+        assert::close(
+            (1.0 - d.inverse_survival(1e-30)) / 6.2996052495735786e-11,
+            1.0,
+            1e-5,
+        );
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(1.0, 1.0, 0.0, 1.0).kurtosis(), -6.0 / 5.0);
@@ -330,6 +515,34 @@ mod tests {
         assert_eq!(new!(3.0, 2.0, -1.0, 2.0).kurtosis(), -0.6428571428571429);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        for &x in &[-1.5, -1.0, -0.5, 0.0, 0.5, 1.5, 2.5] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        for &x in &[-1.5, -1.0, -0.5, 0.0, 0.5, 1.5, 2.5] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+        let d = new!(2.0, 3.0, 0.0, 1.0);
+        assert::close(d.ln_distribution(1e-10), -4.4259942390786192e+01, 1e-12);
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        for &x in &[-1.5, -1.0, -0.5, 0.0, 0.5, 1.5, 2.5] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-14);
+        }
+        let d = new!(2.0, 3.0, 0.0, 1.0);
+        assert::close(d.ln_survival(1.0 - 1e-10), -6.7691258180555379e+01, 1e-9);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(0.5, 0.5, 0.0, 1.0).mean(), 0.5);
@@ -386,6 +599,31 @@ mod tests {
         assert_eq!(new!(3.0, 2.0, -1.0, 2.0).skewness(), -0.28571428571428575);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(2.0, 3.0, -1.0, 2.0);
+        for &x in &[-1.5, -1.0, 0.0, 0.5, 1.5, 2.0, 2.5] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-14);
+        }
+        let d = new!(2.0, 3.0, 0.0, 1.0);
+        assert::close(d.survival(1.0 - 1e-10) / 4.000000992584534e-30, 1.0, 1e-5);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Beta::try_new(2.0, 3.0, -1.0, 2.0).is_ok());
+        assert_eq!(
+            Beta::try_new(0.0, 3.0, -1.0, 2.0).unwrap_err(),
+            Error::NotPositive("alpha")
+        );
+        assert_eq!(
+            Beta::try_new(2.0, 3.0, 2.0, 2.0).unwrap_err(),
+            Error::NotLess("a", "b")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(1.0, 1.0, 0.0, 1.0).variance(), 1.0 / 12.0);
@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// A binomial distribution.
@@ -32,6 +32,13 @@ impl Binomial {
         }
     }
 
+    /// Create a binomial distribution with `n` trails and success probability
+    /// `p` or fail if the parameters are invalid.
+    pub fn try_new(n: usize, p: f64) -> Result<Self, Error> {
+        error::probability(p, "p")?;
+        Ok(Binomial::new(n, p))
+    }
+
     /// Create a binomial distribution with `n` trails and failure probability
     /// `q`.
     ///
@@ -52,6 +59,13 @@ impl Binomial {
         }
     }
 
+    /// Create a binomial distribution with `n` trails and failure probability
+    /// `q` or fail if the parameters are invalid.
+    pub fn try_with_failure(n: usize, q: f64) -> Result<Self, Error> {
+        error::probability(q, "q")?;
+        Ok(Binomial::with_failure(n, q))
+    }
+
     /// Return the number of trials.
     #[inline(always)]
     pub fn n(&self) -> usize {
@@ -107,6 +121,37 @@ impl distribution::Discrete for Binomial {
             ln_c.exp() * (n / (2.0 * PI * x * (n_m_x))).sqrt()
         }
     }
+
+    fn ln_mass(&self, x: usize) -> f64 {
+        use std::f64::consts::PI;
+        use std::f64::NEG_INFINITY;
+
+        if x > self.n {
+            return NEG_INFINITY;
+        }
+        if self.p == 0.0 {
+            return if x == 0 { 0.0 } else { NEG_INFINITY };
+        }
+        if self.p == 1.0 {
+            return if x == self.n { 0.0 } else { NEG_INFINITY };
+        }
+
+        let n = self.n as f64;
+        if x == 0 {
+            n * self.q.ln()
+        } else if x == self.n {
+            n * self.p.ln()
+        } else {
+            let x = x as f64;
+            let n_m_x = n - x;
+            let ln_c = stirlerr(n)
+                - stirlerr(x)
+                - stirlerr(n_m_x)
+                - ln_d0(x, self.np)
+                - ln_d0(n_m_x, self.nq);
+            ln_c + 0.5 * (n / (2.0 * PI * x * (n_m_x))).ln()
+        }
+    }
 }
 
 impl distribution::Distribution for Binomial {
@@ -130,6 +175,33 @@ impl distribution::Distribution for Binomial {
         let (p, q) = ((self.n - x) as f64, (x + 1) as f64);
         self.q.inc_beta(p, q, p.ln_beta(q))
     }
+
+    fn ln_distribution(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return ::std::f64::NEG_INFINITY;
+        }
+        let x = x as usize;
+        if x == 0 {
+            return self.n as f64 * self.q.ln();
+        }
+        self.distribution(x as f64).ln()
+    }
+
+    /// Compute the survival function.
+    ///
+    /// The implementation is based on the incomplete beta function.
+    fn survival(&self, x: f64) -> f64 {
+        use special::Beta;
+        if x < 0.0 {
+            return 1.0;
+        }
+        let x = x as usize;
+        if x >= self.n {
+            return 0.0;
+        }
+        let (p, q) = ((x + 1) as f64, (self.n - x) as f64);
+        self.p.inc_beta(p, q, p.ln_beta(q))
+    }
 }
 
 impl distribution::Entropy for Binomial {
@@ -146,6 +218,93 @@ impl distribution::Entropy for Binomial {
     }
 }
 
+impl distribution::Fit for Binomial {
+    /// Estimate the parameters by the method of maximum likelihood.
+    ///
+    /// Both the number of trials and the success probability are estimated.
+    /// The profile likelihood is maximized over the number of trials by
+    /// bracketing and bisection. The estimate exists only when the sample
+    /// variance is positive and less than the sample mean.
+    fn fit(data: &[usize]) -> Result<Estimate<Self>, FitError> {
+        use distribution::Discrete;
+        use std::f64::NEG_INFINITY;
+
+        if data.len() < 2 {
+            return Err(FitError::Insufficient);
+        }
+        let mean = fit::mean(data.iter().map(|&x| x as f64));
+        let variance = fit::variance(data.iter().map(|&x| x as f64), mean);
+        if variance == 0.0 || variance >= mean {
+            return Err(FitError::Degenerate);
+        }
+        let ln_likelihood = |n: usize| {
+            if mean >= n as f64 {
+                return NEG_INFINITY;
+            }
+            let distribution = Binomial::new(n, mean / n as f64);
+            data.iter()
+                .fold(0.0, |sum, &x| sum + distribution.ln_mass(x))
+        };
+        let increasing = |n: usize| ln_likelihood(n + 1) > ln_likelihood(n);
+
+        let mut lower = data.iter().fold(1, |n, &x| n.max(x));
+        let mut upper = lower;
+        let mut converged = true;
+        let mut iterations = 0;
+        while increasing(upper) {
+            iterations += 1;
+            if iterations == fit::ITERATIONS {
+                converged = false;
+                break;
+            }
+            lower = upper;
+            upper = match upper.checked_mul(2) {
+                Some(upper) => upper,
+                _ => return Err(FitError::Degenerate),
+            };
+        }
+        while upper - lower > 1 {
+            iterations += 1;
+            let middle = lower + (upper - lower) / 2;
+            if increasing(middle) {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        let n = if increasing(lower) { upper } else { lower };
+        Ok(fit::discrete(
+            Binomial::try_new(n, mean / n as f64)?,
+            data,
+            iterations,
+            converged,
+        ))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    ///
+    /// Both the number of trials and the success probability are estimated.
+    /// The estimate exists only when the sample variance is positive and less
+    /// than the sample mean.
+    fn fit_moments(data: &[usize]) -> Result<Estimate<Self>, FitError> {
+        if data.len() < 2 {
+            return Err(FitError::Insufficient);
+        }
+        let mean = fit::mean(data.iter().map(|&x| x as f64));
+        let variance = fit::variance(data.iter().map(|&x| x as f64), mean);
+        if variance == 0.0 || variance >= mean {
+            return Err(FitError::Degenerate);
+        }
+        let n = ((mean * mean / (mean - variance)).round() as usize).max(1);
+        Ok(fit::discrete(
+            Binomial::try_new(n, mean / n as f64)?,
+            data,
+            0,
+            true,
+        ))
+    }
+}
+
 impl distribution::Inverse for Binomial {
     /// Compute the inverse of the cumulative distribution function.
     ///
@@ -221,6 +380,30 @@ impl distribution::Inverse for Binomial {
             m
         }
     }
+
+    /// Compute the inverse of the survival function.
+    ///
+    /// The survival function is inverted by bisection, which retains accuracy
+    /// in the upper tail.
+    fn inverse_survival(&self, q: f64) -> usize {
+        use distribution::Distribution;
+
+        should!(0.0 <= q && q <= 1.0);
+
+        if self.survival(0.0) <= q {
+            return 0;
+        }
+        let (mut lower, mut upper) = (0, self.n);
+        while upper - lower > 1 {
+            let middle = lower + (upper - lower) / 2;
+            if self.survival(middle as f64) > q {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        upper
+    }
 }
 
 impl distribution::Kurtosis for Binomial {
@@ -343,7 +526,7 @@ fn approximate_by_normal(p: f64, np: f64, v: f64, u: f64) -> f64 {
 }
 
 // strilerr(n) = ln(n!) - ln(sqrt(2π * n) * (n / e)^n)
-fn stirlerr(n: f64) -> f64 {
+pub fn stirlerr(n: f64) -> f64 {
     const S0: f64 = 1.0 / 12.0;
     const S1: f64 = 1.0 / 360.0;
     const S2: f64 = 1.0 / 1260.0;
@@ -363,7 +546,7 @@ fn stirlerr(n: f64) -> f64 {
         1.041126526197209650e-02,
         9.255462182712732918e-03,
         8.330563433362871256e-03,
-        7.757367548795184079e-03,
+        7.573675487951840795e-03,
         6.942840107209529866e-03,
         6.408994188004207068e-03,
         5.951370112758847736e-03,
@@ -388,7 +571,7 @@ fn stirlerr(n: f64) -> f64 {
 }
 
 // ln(np * D₀) = x * ln(x / np) + np - x
-fn ln_d0(x: f64, np: f64) -> f64 {
+pub fn ln_d0(x: f64, np: f64) -> f64 {
     if (x - np).abs() < 0.1 * (x + np) {
         // ε = (n / np) is close to 1. Use a series expansion.
         let mut s = (x - np).powi(2) / (x + np);
@@ -448,10 +631,46 @@ mod tests {
 
     #[test]
     fn entropy() {
-        assert_eq!(new!(16, 0.25).entropy(), 1.9588018945068573);
+        assert_eq!(new!(16, 0.25).entropy(), 1.95882585314307);
         assert_eq!(new!(10_000_000, 0.5).entropy(), 8.784839178123887);
     }
 
+    #[test]
+    fn fit() {
+        use distribution::FitError;
+
+        let data = vec![6, 4, 7, 5, 8, 3, 6, 6, 5, 9, 4, 7];
+        let estimate = Binomial::fit(&data).unwrap();
+        assert_eq!(estimate.distribution.n(), 11);
+        assert::close(estimate.distribution.p(), 5.3030303030303028e-01, 1e-15);
+        assert::close(estimate.ln_likelihood, -2.3159435758865715e+01, 1e-12);
+        assert!(estimate.converged);
+
+        assert_eq!(
+            Binomial::fit(&[0, 5, 10]).unwrap_err(),
+            FitError::Degenerate
+        );
+        assert_eq!(
+            Binomial::fit(&[5, 5, 5]).unwrap_err(),
+            FitError::Degenerate
+        );
+    }
+
+    #[test]
+    fn fit_moments() {
+        use distribution::FitError;
+
+        let data = vec![6, 4, 7, 5, 8, 3, 6, 6, 5, 9, 4, 7];
+        let estimate = Binomial::fit_moments(&data).unwrap();
+        assert_eq!(estimate.distribution.n(), 11);
+        assert::close(estimate.distribution.p(), 5.3030303030303028e-01, 1e-15);
+
+        assert_eq!(
+            Binomial::fit_moments(&[5, 5, 5]).unwrap_err(),
+            FitError::Degenerate
+        );
+    }
+
     #[test]
     fn inverse() {
         let d = Binomial::new(250, 0.55);
@@ -469,11 +688,55 @@ mod tests {
         assert_eq!(new!(1_000_000_000, 6.66e-9).inverse(0.8), 8);
     }
 
+    #[test]
+    fn inverse_survival() {
+        for &(n, p) in &[(16, 0.25), (10000, 0.5)] {
+            let d = new!(n, p);
+            for &q in &[1e-300, 1e-20, 0.001, 0.5, 0.9, 1.0] {
+                let x = d.inverse_survival(q);
+                assert!(d.survival(x as f64) <= q);
+                assert!(x == 0 || d.survival(x as f64 - 1.0) > q);
+            }
+        }
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(16, 0.25).kurtosis(), -0.041666666666666664);
     }
 
+    #[test]
+    fn ln_distribution() {
+        let d = new!(16, 0.25);
+        for x in -1..18 {
+            let x = x as f64;
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_mass() {
+        let d = new!(16, 0.25);
+        for x in 0..17 {
+            assert::close(d.ln_mass(x), d.mass(x).ln(), 1e-13);
+        }
+
+        let d = new!(10000, 0.5);
+        assert::close(d.ln_mass(0), 10000.0 * 0.5f64.ln(), 1e-10);
+        assert::close(d.ln_mass(100) / -6.3746737939239824e+03, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(16, 0.25);
+        for x in -1..18 {
+            let x = x as f64;
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-13);
+        }
+        assert::close(d.ln_survival(12.0), -1.2484923130515474e+01, 1e-13);
+        assert::close(d.ln_survival(15.0), 16.0 * 0.25f64.ln(), 1e-13);
+    }
+
     #[test]
     fn mass() {
         let d = new!(16, 0.25);
@@ -522,6 +785,31 @@ mod tests {
         assert_eq!(new!(16, 0.25).skewness(), 0.2886751345948129);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(16, 0.25);
+        for &x in &[-1.0, 0.0, 1.0, 4.0, 8.5, 16.0, 20.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-14);
+        }
+        assert::close(d.survival(12.0) / 3.7832651287317276e-06, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Binomial::try_new(16, 0.25).is_ok());
+        assert!(Binomial::try_with_failure(16, 0.25).is_ok());
+        assert_eq!(
+            Binomial::try_new(16, -0.25).unwrap_err(),
+            Error::NotProbability("p")
+        );
+        assert_eq!(
+            Binomial::try_with_failure(16, 1.0).unwrap_err(),
+            Error::NotProbability("q")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(16, 0.25).variance(), 3.0);
@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// A categorical distribution.
@@ -7,6 +7,14 @@ pub struct Categorical {
     k: usize,
     p: Vec<f64>,
     cumsum: Vec<f64>,
+    alias: Option<Alias>,
+}
+
+// An alias table, which allows for drawing samples in constant time.
+#[derive(Clone, Debug)]
+struct Alias {
+    probability: Vec<f64>,
+    alias: Vec<usize>,
 }
 
 impl Categorical {
@@ -19,18 +27,95 @@ impl Categorical {
             p.iter().all(|&p| p >= 0.0 && p <= 1.0)
                 && (p.iter().fold(0.0, |sum, &p| sum + p) - 1.0).abs() < EPSILON
         });
+        Categorical::build(p.to_vec())
+    }
 
-        let k = p.len();
-        let mut cumsum = p.to_vec();
-        for i in 1..(k - 1) {
-            cumsum[i] += cumsum[i - 1];
+    /// Create a categorical distribution with success probability `p` or fail
+    /// if the parameter is invalid.
+    pub fn try_new(p: &[f64]) -> Result<Self, Error> {
+        error::probability_vector(p, "p")?;
+        Ok(Categorical::new(p))
+    }
+
+    /// Create a categorical distribution with success probability proportional
+    /// to `weights` or fail if the parameters are invalid.
+    ///
+    /// The weights should be finite and nonnegative, and at least one of them
+    /// should be positive.
+    pub fn from_weights(weights: &[f64]) -> Result<Self, Error> {
+        if weights.is_empty() {
+            return Err(Error::Empty("weights"));
         }
-        cumsum[k - 1] = 1.0;
-        Categorical {
-            k: k,
-            p: p.to_vec(),
-            cumsum: cumsum,
+        let mut sum = 0.0;
+        for &w in weights {
+            error::non_negative(w, "weights")?;
+            sum += w;
+        }
+        error::finite(sum, "weights")?;
+        if sum == 0.0 {
+            return Err(Error::Zero("weights"));
+        }
+        Ok(Categorical::build(
+            weights.iter().map(|&w| w / sum).collect(),
+        ))
+    }
+
+    /// Create a categorical distribution with success probability proportional
+    /// to the exponent of `ln_weights` or fail if the parameters are invalid.
+    ///
+    /// The normalization is performed in the logarithmic domain, which is
+    /// numerically stable even when the weights are far outside the range of
+    /// floating-point numbers. None of the logarithms should be positive
+    /// infinity or not a number, and at least one of them should be finite.
+    pub fn from_log_weights(ln_weights: &[f64]) -> Result<Self, Error> {
+        if ln_weights.is_empty() {
+            return Err(Error::Empty("ln_weights"));
         }
+        let mut max = ::std::f64::NEG_INFINITY;
+        for &w in ln_weights {
+            if w.is_nan() || w == ::std::f64::INFINITY {
+                return Err(Error::NotFinite("ln_weights"));
+            }
+            max = max.max(w);
+        }
+        if max == ::std::f64::NEG_INFINITY {
+            return Err(Error::Zero("ln_weights"));
+        }
+        let mut p = ln_weights
+            .iter()
+            .map(|&w| (w - max).exp())
+            .collect::<Vec<_>>();
+        let sum = p.iter().fold(0.0, |sum, &p| sum + p);
+        for p in &mut p {
+            *p /= sum;
+        }
+        Ok(Categorical::build(p))
+    }
+
+    /// Create a categorical distribution with success probability `p` that
+    /// draws samples using an alias table.
+    ///
+    /// It should hold that `p[i] >= 0`, `p[i] <= 1`, and `sum(p) == 1`. The
+    /// table is built once in linear time and then allows for drawing samples
+    /// in constant time, which is preferable when the number of categories is
+    /// large. The rest of the functionality is unaffected.
+    pub fn with_alias(p: &[f64]) -> Self {
+        let mut distribution = Categorical::new(p);
+        distribution.alias = Some(Alias::new(p));
+        distribution
+    }
+
+    /// Create a categorical distribution with success probability `p` that
+    /// draws samples using an alias table or fail if the parameter is invalid.
+    pub fn try_with_alias(p: &[f64]) -> Result<Self, Error> {
+        error::probability_vector(p, "p")?;
+        Ok(Categorical::with_alias(p))
+    }
+
+    /// Check if samples are drawn using an alias table.
+    #[inline(always)]
+    pub fn has_alias(&self) -> bool {
+        self.alias.is_some()
     }
 
     /// Return the number of categories.
@@ -44,6 +129,70 @@ impl Categorical {
     pub fn p(&self) -> &[f64] {
         &self.p
     }
+
+    fn build(p: Vec<f64>) -> Self {
+        let k = p.len();
+        let mut cumsum = p.clone();
+        for i in 1..(k - 1) {
+            cumsum[i] += cumsum[i - 1];
+        }
+        cumsum[k - 1] = 1.0;
+        Categorical {
+            k: k,
+            p: p,
+            cumsum: cumsum,
+            alias: None,
+        }
+    }
+}
+
+impl Alias {
+    // Build the table using Vose's method.
+    fn new(p: &[f64]) -> Self {
+        let k = p.len();
+        let mut probability = p.iter().map(|&p| p * k as f64).collect::<Vec<_>>();
+        let mut alias = (0..k).collect::<Vec<_>>();
+        let (mut small, mut large): (Vec<_>, Vec<_>) = (0..k).partition(|&i| probability[i] < 1.0);
+        while let (Some(&i), Some(&j)) = (small.last(), large.last()) {
+            small.pop();
+            alias[i] = j;
+            probability[j] -= 1.0 - probability[i];
+            if probability[j] < 1.0 {
+                large.pop();
+                small.push(j);
+            }
+        }
+        // The leftovers are due to rounding errors. Categories with zero
+        // probability are redirected to a most probable one so that they are
+        // never drawn.
+        let mode = (0..k).fold(0, |mode, i| if p[i] > p[mode] { i } else { mode });
+        for i in small.into_iter().chain(large) {
+            if p[i] > 0.0 {
+                probability[i] = 1.0;
+            } else {
+                probability[i] = 0.0;
+                alias[i] = mode;
+            }
+        }
+        Alias {
+            probability: probability,
+            alias: alias,
+        }
+    }
+
+    fn sample<S>(&self, source: &mut S) -> usize
+    where
+        S: Source,
+    {
+        let k = self.alias.len();
+        let u = source.read::<f64>() * k as f64;
+        let i = (u as usize).min(k - 1);
+        if u - (i as f64) < self.probability[i] {
+            i
+        } else {
+            self.alias[i]
+        }
+    }
 }
 
 impl distribution::Discrete for Categorical {
@@ -52,6 +201,12 @@ impl distribution::Discrete for Categorical {
         should!(x < self.k);
         self.p[x]
     }
+
+    #[inline]
+    fn ln_mass(&self, x: usize) -> f64 {
+        should!(x < self.k);
+        self.p[x].ln()
+    }
 }
 
 impl distribution::Distribution for Categorical {
@@ -67,6 +222,17 @@ impl distribution::Distribution for Categorical {
         }
         self.cumsum[x]
     }
+
+    fn survival(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 1.0;
+        }
+        let x = x as usize;
+        if x + 1 >= self.k {
+            return 0.0;
+        }
+        self.p[(x + 1)..].iter().fold(0.0, |sum, &p| sum + p)
+    }
 }
 
 impl distribution::Entropy for Categorical {
@@ -75,13 +241,47 @@ impl distribution::Entropy for Categorical {
     }
 }
 
+impl distribution::Fit for Categorical {
+    /// Estimate the parameters by the method of maximum likelihood.
+    ///
+    /// The number of categories is taken to be one plus the largest
+    /// observation.
+    fn fit(data: &[usize]) -> Result<Estimate<Self>, FitError> {
+        if data.is_empty() {
+            return Err(FitError::Insufficient);
+        }
+        let k = data.iter().fold(0, |k, &x| k.max(x + 1));
+        let mut p = vec![0.0; k];
+        for &x in data {
+            p[x] += 1.0;
+        }
+        let n = data.len() as f64;
+        for p in &mut p {
+            *p /= n;
+        }
+        Ok(fit::discrete(Categorical::try_new(&p)?, data, 0, true))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    ///
+    /// The estimate coincides with the maximum-likelihood one.
+    #[inline]
+    fn fit_moments(data: &[usize]) -> Result<Estimate<Self>, FitError> {
+        Self::fit(data)
+    }
+}
+
 impl distribution::Inverse for Categorical {
     fn inverse(&self, p: f64) -> usize {
+        use std::cmp::Ordering::*;
         should!(0.0 <= p && p <= 1.0);
-        self.cumsum
-            .iter()
-            .position(|&sum| sum > 0.0 && sum >= p)
-            .unwrap_or_else(|| self.p.iter().rposition(|&p| p > 0.0).unwrap())
+        match self
+            .cumsum
+            .binary_search_by(|&sum| if sum > 0.0 && sum >= p { Greater } else { Less })
+        {
+            Err(i) if i < self.k => i,
+            _ => self.p.iter().rposition(|&p| p > 0.0).unwrap(),
+        }
     }
 }
 
@@ -150,7 +350,10 @@ impl distribution::Sample for Categorical {
         S: Source,
     {
         use distribution::Inverse;
-        self.inverse(source.read::<f64>())
+        match self.alias {
+            Some(ref alias) => alias.sample(source),
+            _ => self.inverse(source.read::<f64>()),
+        }
     }
 }
 
@@ -223,6 +426,78 @@ mod tests {
         assert_eq!(new!([0.1, 0.2, 0.3, 0.4]).entropy(), 1.2798542258336676);
     }
 
+    #[test]
+    fn fit() {
+        let data = vec![1, 3, 1, 2, 3, 3, 1, 3];
+        let estimate = Categorical::fit(&data).unwrap();
+        assert_eq!(estimate.distribution.p(), &[0.0, 0.375, 0.125, 0.5]);
+        assert::close(
+            estimate.ln_likelihood,
+            3.0 * 0.375f64.ln() + 0.125f64.ln() + 4.0 * 0.5f64.ln(),
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![1, 3, 1, 2, 3, 3, 1, 3];
+        let estimate = Categorical::fit_moments(&data).unwrap();
+        assert_eq!(estimate.distribution.p(), &[0.0, 0.375, 0.125, 0.5]);
+    }
+
+    #[test]
+    fn from_log_weights() {
+        use distribution::Error;
+
+        let d = Categorical::from_log_weights(&[1000.0, 1000.0 + 3f64.ln()]).unwrap();
+        assert::close(d.p(), &[0.25, 0.75][..], 1e-12);
+
+        let d = Categorical::from_log_weights(&[-1e4, ::std::f64::NEG_INFINITY, -1e4]).unwrap();
+        assert_eq!(d.p(), &[0.5, 0.0, 0.5]);
+
+        assert_eq!(
+            Categorical::from_log_weights(&[]).unwrap_err(),
+            Error::Empty("ln_weights")
+        );
+        assert_eq!(
+            Categorical::from_log_weights(&[0.0, ::std::f64::NAN]).unwrap_err(),
+            Error::NotFinite("ln_weights")
+        );
+        assert_eq!(
+            Categorical::from_log_weights(&[::std::f64::NEG_INFINITY; 2]).unwrap_err(),
+            Error::Zero("ln_weights")
+        );
+    }
+
+    #[test]
+    fn from_weights() {
+        use distribution::Error;
+
+        let d = Categorical::from_weights(&[2.0, 0.0, 6.0]).unwrap();
+        assert_eq!(d.p(), &[0.25, 0.0, 0.75]);
+
+        let weights = vec![1.0; 100000];
+        let d = Categorical::from_weights(&weights).unwrap();
+        assert_eq!(d.distribution(99999.0), 1.0);
+
+        assert_eq!(
+            Categorical::from_weights(&[]).unwrap_err(),
+            Error::Empty("weights")
+        );
+        assert_eq!(
+            Categorical::from_weights(&[1.0, -1.0]).unwrap_err(),
+            Error::Negative("weights")
+        );
+        assert_eq!(
+            Categorical::from_weights(&[1.0, ::std::f64::NAN]).unwrap_err(),
+            Error::NotFinite("weights")
+        );
+        assert_eq!(
+            Categorical::from_weights(&[0.0, 0.0]).unwrap_err(),
+            Error::Zero("weights")
+        );
+    }
+
     #[test]
     fn inverse() {
         let d = new!([0.0, 0.75, 0.25, 0.0]);
@@ -238,6 +513,13 @@ mod tests {
             &p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
             &vec![0, 1, 2, 2]
         );
+
+        let d = new!([0.25, 0.0, 0.0, 0.5, 0.25]);
+        let p = vec![0.0, 0.25, 0.2500001, 0.75, 0.7500001, 1.0];
+        assert_eq!(
+            &p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
+            &vec![0, 0, 3, 3, 4, 4]
+        );
     }
 
     #[test]
@@ -246,6 +528,16 @@ mod tests {
         assert_eq!(new!([0.1, 0.2, 0.3, 0.4]).kurtosis(), -0.7999999999999998);
     }
 
+    #[test]
+    fn ln_mass() {
+        let p = [0.0, 0.75, 0.25, 0.0];
+        let d = new!(p);
+        assert_eq!(
+            &(0..4).map(|x| d.ln_mass(x)).collect::<Vec<_>>(),
+            &p.iter().map(|p| p.ln()).collect::<Vec<_>>()
+        );
+    }
+
     #[test]
     fn mass() {
         let p = [0.0, 0.75, 0.25, 0.0];
@@ -319,6 +611,35 @@ mod tests {
         assert_eq!(new!([0.1, 0.2, 0.3, 0.4]).skewness(), -0.6);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!([0.1, 0.2, 0.3, 0.4]);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 2.5, 3.0, 4.0];
+        let p = vec![1.0, 0.9, 0.9, 0.7, 0.4, 0.0, 0.0];
+        assert::close(
+            &x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Categorical::try_new(&[0.1, 0.2, 0.3, 0.4]).is_ok());
+        assert_eq!(Categorical::try_new(&[]).unwrap_err(), Error::Empty("p"));
+        assert_eq!(
+            Categorical::try_new(&[0.1, 0.2, 0.3]).unwrap_err(),
+            Error::NotNormalized("p")
+        );
+        assert!(Categorical::try_with_alias(&[0.1, 0.2, 0.3, 0.4]).is_ok());
+        assert_eq!(
+            Categorical::try_with_alias(&[0.1, -0.2, 1.1]).unwrap_err(),
+            Error::Negative("p")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(equal 3).variance(), 2.0 / 3.0);
@@ -327,4 +648,28 @@ mod tests {
             11.0 / 12.0
         );
     }
+
+    #[test]
+    fn with_alias() {
+        let p = [0.1, 0.0, 0.3, 0.0, 0.6];
+        let d = Categorical::with_alias(&p);
+        assert!(d.has_alias() && !new!(p).has_alias());
+        assert_eq!(d.p(), &p);
+        assert_eq!(d.mean(), new!(p).mean());
+
+        let mut source = source::default();
+        let n = 100000;
+        let mut count = [0; 5];
+        for x in Independent(&d, &mut source).take(n) {
+            count[x] += 1;
+        }
+        assert_eq!(count[1], 0);
+        assert_eq!(count[3], 0);
+        for (&count, &p) in count.iter().zip(&p) {
+            assert!((count as f64 / n as f64 - p).abs() < 0.01);
+        }
+
+        let d = Categorical::with_alias(&[1.0]);
+        assert!(Independent(&d, &mut source).take(100).all(|x| x == 0));
+    }
 }
@@ -0,0 +1,348 @@
+use distribution::{self, error, Error, Gamma};
+use source::Source;
+
+/// A chi-squared distribution.
+#[derive(Clone, Copy, Debug)]
+pub struct ChiSquared {
+    k: f64,
+    gamma: Gamma,
+}
+
+impl ChiSquared {
+    /// Create a chi-squared distribution with `k` degrees of freedom.
+    ///
+    /// It should hold that `k > 0`.
+    #[inline]
+    pub fn new(k: f64) -> Self {
+        should!(k > 0.0);
+        ChiSquared {
+            k: k,
+            gamma: Gamma::new(0.5 * k, 2.0),
+        }
+    }
+
+    /// Create a chi-squared distribution with `k` degrees of freedom or fail if
+    /// the parameter is invalid.
+    #[inline]
+    pub fn try_new(k: f64) -> Result<Self, Error> {
+        error::positive(k, "k")?;
+        Ok(ChiSquared::new(k))
+    }
+
+    /// Return the degrees of freedom.
+    #[inline(always)]
+    pub fn k(&self) -> f64 {
+        self.k
+    }
+}
+
+impl distribution::Continuous for ChiSquared {
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        self.gamma.density(x)
+    }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        self.gamma.ln_density(x)
+    }
+}
+
+impl distribution::Distribution for ChiSquared {
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        self.gamma.distribution(x)
+    }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        self.gamma.ln_distribution(x)
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        self.gamma.ln_survival(x)
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        self.gamma.survival(x)
+    }
+}
+
+impl distribution::Entropy for ChiSquared {
+    #[inline]
+    fn entropy(&self) -> f64 {
+        self.gamma.entropy()
+    }
+}
+
+impl distribution::Inverse for ChiSquared {
+    #[inline]
+    fn inverse(&self, p: f64) -> f64 {
+        use distribution::gamma;
+        2.0 * gamma::inverse(p, 0.5 * self.k)
+    }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        use distribution::gamma;
+        2.0 * gamma::inverse_survival(q, 0.5 * self.k)
+    }
+}
+
+impl distribution::Kurtosis for ChiSquared {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        12.0 / self.k
+    }
+}
+
+impl distribution::Mean for ChiSquared {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.k
+    }
+}
+
+impl distribution::Median for ChiSquared {
+    fn median(&self) -> f64 {
+        use distribution::Inverse;
+        self.inverse(0.5)
+    }
+}
+
+impl distribution::Modes for ChiSquared {
+    #[inline]
+    fn modes(&self) -> Vec<f64> {
+        self.gamma.modes()
+    }
+}
+
+impl distribution::Sample for ChiSquared {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        self.gamma.sample(source)
+    }
+}
+
+impl distribution::Skewness for ChiSquared {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        (8.0 / self.k).sqrt()
+    }
+}
+
+impl distribution::Variance for ChiSquared {
+    #[inline]
+    fn variance(&self) -> f64 {
+        2.0 * self.k
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($k:expr) => (ChiSquared::new($k));
+    );
+
+    #[test]
+    fn density() {
+        let d = new!(5.0);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 2.0, 3.0, 4.0, 6.0, 8.0, 12.0, 20.0];
+        let p = vec![
+            0.0000000000000000e+00,
+            0.0000000000000000e+00,
+            3.6615940788976870e-02,
+            8.0656908173047784e-02,
+            1.3836916580686490e-01,
+            1.5418032980376928e-01,
+            1.4397591070183480e-01,
+            9.7304346659282920e-02,
+            5.5111960944245475e-02,
+            1.3702310000441040e-02,
+            5.3999406373927451e-04,
+        ];
+
+        assert::close(
+            &x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(5.0);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 2.0, 3.0, 4.0, 6.0, 8.0, 12.0, 20.0];
+        let p = vec![
+            0.0000000000000000e+00,
+            0.0000000000000000e+00,
+            7.8767067673704075e-03,
+            3.7434226752703630e-02,
+            1.5085496391539036e-01,
+            3.0001416412137250e-01,
+            4.5058404864721979e-01,
+            6.9378108158672158e-01,
+            8.4376437242227764e-01,
+            9.6521221949375813e-01,
+            9.9875026943696865e-01,
+        ];
+
+        assert::close(
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            &p,
+            1e-14,
+        );
+
+        let gamma = Gamma::new(2.5, 2.0);
+        assert::close(
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            &x.iter().map(|&x| gamma.distribution(x)).collect::<Vec<_>>(),
+            0.0,
+        );
+    }
+
+    #[test]
+    fn entropy() {
+        assert::close(new!(5.0).entropy(), 2.423095090065, 1e-14);
+    }
+
+    #[test]
+    fn inverse() {
+        use std::f64::INFINITY;
+
+        let d = new!(5.0);
+        let p = vec![
+            0.0, 0.001, 0.05, 0.1, 0.25, 0.5, 0.75, 0.9, 0.95, 0.999, 1.0,
+        ];
+        let x = vec![
+            0.0000000000000000e+00,
+            2.1021260262921918e-01,
+            1.1454762260617692e+00,
+            1.6103079869623229e+00,
+            2.6746028094321632e+00,
+            4.3514601910955273e+00,
+            6.6256797638292504e+00,
+            9.2363568997811178e+00,
+            1.1070497693516355e+01,
+            2.0515005652432880e+01,
+            INFINITY,
+        ];
+
+        assert::close(
+            &p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
+            &x,
+            1e-13,
+        );
+
+        let d = new!(0.5);
+        let p = vec![1e-10, 0.1, 0.5, 0.9];
+        let x = vec![
+            1.3499395786223460e-40,
+            1.3500124771267930e-04,
+            8.7347604705746817e-02,
+            1.5007857444736712e+00,
+        ];
+        for (&p, &x) in p.iter().zip(x.iter()) {
+            assert::close(d.inverse(p) / x, 1.0, 1e-13);
+        }
+    }
+
+    #[test]
+    fn inverse_survival() {
+        let d = new!(5.0);
+        for &q in &[0.001, 0.1, 0.5, 0.9, 0.999] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-12);
+        }
+        assert::close(
+            d.inverse_survival(1e-20) / 1.0342897723775795e+02,
+            1.0,
+            1e-13,
+        );
+    }
+
+    #[test]
+    fn kurtosis() {
+        assert_eq!(new!(6.0).kurtosis(), 2.0);
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = new!(5.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 5.0, 20.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(5.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 5.0, 20.0] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-13);
+        }
+        assert::close(d.ln_survival(500.0) / -2.4199649751735857e+02, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!(5.0).mean(), 5.0);
+    }
+
+    #[test]
+    fn median() {
+        assert::close(new!(5.0).median(), 4.3514601910955282e+00, 1e-14);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(new!(5.0).modes(), vec![3.0]);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!(5.0);
+        let n = 10000;
+        let sum = Independent(&d, &mut source::default())
+            .take(n)
+            .fold(0.0, |a, b| a + b);
+        assert!((sum / n as f64 - 5.0).abs() < 5.0 * (d.variance() / n as f64).sqrt());
+    }
+
+    #[test]
+    fn skewness() {
+        assert_eq!(new!(8.0).skewness(), 1.0);
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!(5.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 5.0, 20.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-14);
+        }
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(ChiSquared::try_new(5.0).is_ok());
+        assert_eq!(
+            ChiSquared::try_new(-5.0).unwrap_err(),
+            Error::NotPositive("k")
+        );
+    }
+
+    #[test]
+    fn variance() {
+        assert_eq!(new!(5.0).variance(), 10.0);
+    }
+}
@@ -0,0 +1,229 @@
+use distribution::{error, Error};
+use source::Source;
+
+/// A Dirichlet distribution.
+#[derive(Clone, Debug)]
+pub struct Dirichlet {
+    k: usize,
+    alpha: Vec<f64>,
+    alpha0: f64,
+    ln_beta: f64,
+}
+
+impl Dirichlet {
+    /// Create a Dirichlet distribution with concentration parameters `alpha`.
+    ///
+    /// It should hold that `alpha[i] > 0`.
+    pub fn new(alpha: &[f64]) -> Self {
+        use special::Gamma;
+        should!(!alpha.is_empty() && alpha.iter().all(|&alpha| alpha > 0.0));
+        let alpha0 = alpha.iter().fold(0.0, |sum, &alpha| sum + alpha);
+        let ln_beta = alpha
+            .iter()
+            .fold(0.0, |sum, &alpha| sum + alpha.ln_gamma().0)
+            - alpha0.ln_gamma().0;
+        Dirichlet {
+            k: alpha.len(),
+            alpha: alpha.to_vec(),
+            alpha0: alpha0,
+            ln_beta: ln_beta,
+        }
+    }
+
+    /// Create a Dirichlet distribution with concentration parameters `alpha`
+    /// or fail if the parameters are invalid.
+    pub fn try_new(alpha: &[f64]) -> Result<Self, Error> {
+        if alpha.is_empty() {
+            return Err(Error::Empty("alpha"));
+        }
+        for &alpha in alpha {
+            error::positive(alpha, "alpha")?;
+        }
+        Ok(Dirichlet::new(alpha))
+    }
+
+    /// Return the number of categories.
+    #[inline(always)]
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Return the concentration parameters.
+    #[inline(always)]
+    pub fn alpha(&self) -> &[f64] {
+        &self.alpha
+    }
+
+    /// Compute the covariance matrix in row-major order.
+    pub fn covariance(&self) -> Vec<f64> {
+        let k = self.k;
+        let norm = self.alpha0 * self.alpha0 * (self.alpha0 + 1.0);
+        let mut covariance = vec![0.0; k * k];
+        for i in 0..k {
+            for j in 0..k {
+                covariance[i * k + j] = if i == j {
+                    self.alpha[i] * (self.alpha0 - self.alpha[i]) / norm
+                } else {
+                    -self.alpha[i] * self.alpha[j] / norm
+                };
+            }
+        }
+        covariance
+    }
+
+    /// Compute the probability density function.
+    #[inline]
+    pub fn density(&self, x: &[f64]) -> f64 {
+        self.ln_density(x).exp()
+    }
+
+    /// Compute the natural logarithm of the probability density function.
+    ///
+    /// It should hold that `x` lies on the probability simplex.
+    pub fn ln_density(&self, x: &[f64]) -> f64 {
+        should!(x.len() == self.k);
+        let mut sum = -self.ln_beta;
+        for (&alpha, &x) in self.alpha.iter().zip(x) {
+            if x < 0.0 {
+                return ::std::f64::NEG_INFINITY;
+            }
+            if alpha != 1.0 {
+                sum += (alpha - 1.0) * x.ln();
+            }
+        }
+        sum
+    }
+
+    /// Compute the mean vector.
+    pub fn mean(&self) -> Vec<f64> {
+        self.alpha
+            .iter()
+            .map(|&alpha| alpha / self.alpha0)
+            .collect()
+    }
+
+    /// Draw a sample and write it into `x`.
+    ///
+    /// The sample is obtained by normalizing independent gamma variates. The
+    /// variates are drawn in the logarithmic domain so that the sample stays
+    /// on the probability simplex even for small concentration parameters,
+    /// which makes it a valid argument for `Categorical::new`.
+    pub fn sample<S>(&self, source: &mut S, x: &mut [f64])
+    where
+        S: Source,
+    {
+        use distribution::gamma;
+
+        should!(x.len() == self.k);
+        let mut max = ::std::f64::NEG_INFINITY;
+        for (x, &alpha) in x.iter_mut().zip(&self.alpha) {
+            *x = if alpha < 1.0 {
+                gamma::sample(1.0 + alpha, source).ln() + source.read::<f64>().ln() / alpha
+            } else {
+                gamma::sample(alpha, source).ln()
+            };
+            if *x > max {
+                max = *x;
+            }
+        }
+        let mut sum = 0.0;
+        for x in x.iter_mut() {
+            *x = (*x - max).exp();
+            sum += *x;
+        }
+        for x in x.iter_mut() {
+            *x /= sum;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($alpha:expr) => (Dirichlet::new(&$alpha));
+    );
+
+    #[test]
+    fn covariance() {
+        let d = new!([2.0, 3.0, 5.0]);
+        let covariance = vec![
+            0.2 * 0.8 / 11.0,
+            -0.2 * 0.3 / 11.0,
+            -0.2 * 0.5 / 11.0,
+            -0.3 * 0.2 / 11.0,
+            0.3 * 0.7 / 11.0,
+            -0.3 * 0.5 / 11.0,
+            -0.5 * 0.2 / 11.0,
+            -0.5 * 0.3 / 11.0,
+            0.5 * 0.5 / 11.0,
+        ];
+        assert::close(&d.covariance(), &covariance, 1e-15);
+    }
+
+    #[test]
+    fn density() {
+        let d = new!([2.0, 3.0, 0.5]);
+        let x = [0.2, 0.5, 0.3];
+        assert::close(d.density(&x), d.ln_density(&x).exp(), 1e-15);
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = new!([2.0, 3.0, 0.5]);
+        let x = vec![[0.2, 0.5, 0.3], [0.6, 0.3, 0.1]];
+        let p = vec![2.9855597274304790e-01, 9.2482315821323102e-01];
+        assert::close(
+            &x.iter().map(|x| d.ln_density(x)).collect::<Vec<_>>(),
+            &p,
+            1e-14,
+        );
+
+        let d = new!([2.0, 3.0]);
+        let beta = Beta::new(2.0, 3.0, 0.0, 1.0);
+        assert::close(d.ln_density(&[0.4, 0.6]), beta.ln_density(0.4), 1e-14);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!([2.0, 3.0, 5.0]).mean(), vec![0.2, 0.3, 0.5]);
+    }
+
+    #[test]
+    fn sample() {
+        let mut source = source::default();
+        let d = new!([2.0, 3.0, 5.0]);
+        let n = 10000;
+        let mut x = [0.0; 3];
+        let mut sum = [0.0; 3];
+        for _ in 0..n {
+            d.sample(&mut source, &mut x);
+            for i in 0..3 {
+                sum[i] += x[i];
+            }
+        }
+        for (&sum, &mean) in sum.iter().zip(&d.mean()) {
+            assert!((sum / n as f64 - mean).abs() < 0.01);
+        }
+
+        let d = new!([1e-3, 1e-3, 1e-3]);
+        for _ in 0..100 {
+            d.sample(&mut source, &mut x);
+            Categorical::new(&x);
+        }
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Dirichlet::try_new(&[2.0, 3.0]).is_ok());
+        assert_eq!(Dirichlet::try_new(&[]).unwrap_err(), Error::Empty("alpha"));
+        assert_eq!(
+            Dirichlet::try_new(&[2.0, 0.0]).unwrap_err(),
+            Error::NotPositive("alpha")
+        );
+    }
+}
@@ -0,0 +1,491 @@
+use std::fmt;
+
+use distribution::{
+    Bernoulli, Beta, Binomial, Categorical, ChiSquared, Exponential, FisherSnedecor, Gamma,
+    Gaussian, Laplace, Logistic, Lognormal, Pert, Poisson, StudentsT, Triangular, Uniform,
+};
+use distribution::{
+    Continuous, Discrete, Distribution, Entropy, Inverse, Kurtosis, Mean, Median, Sample, Skewness,
+    Variance,
+};
+use source::Source;
+
+/// A continuous distribution usable as a trait object.
+///
+/// The trait is implemented for all continuous distributions capable of
+/// drawing samples. Unlike `Sample`, it takes the source of randomness as a
+/// trait object, which makes it possible to keep distributions of different
+/// types in one collection, such as `Vec<Box<dyn DynContinuous>>`.
+pub trait DynContinuous {
+    /// Compute the probability density function.
+    fn density(&self, x: f64) -> f64;
+
+    /// Compute the natural logarithm of the probability density function.
+    fn ln_density(&self, x: f64) -> f64;
+
+    /// Compute the cumulative distribution function.
+    fn distribution(&self, x: f64) -> f64;
+
+    /// Compute the survival function.
+    fn survival(&self, x: f64) -> f64;
+
+    /// Draw a sample.
+    fn sample(&self, source: &mut dyn Source) -> f64;
+}
+
+/// A discrete distribution usable as a trait object.
+///
+/// The trait is implemented for all discrete distributions capable of
+/// drawing samples. Unlike `Sample`, it takes the source of randomness as a
+/// trait object, which makes it possible to keep distributions of different
+/// types in one collection, such as `Vec<Box<dyn DynDiscrete<Value = usize>>>`.
+pub trait DynDiscrete {
+    /// The type of outcomes.
+    type Value;
+
+    /// Compute the probability mass function.
+    fn mass(&self, x: Self::Value) -> f64;
+
+    /// Compute the natural logarithm of the probability mass function.
+    fn ln_mass(&self, x: Self::Value) -> f64;
+
+    /// Compute the cumulative distribution function.
+    fn distribution(&self, x: f64) -> f64;
+
+    /// Compute the survival function.
+    fn survival(&self, x: f64) -> f64;
+
+    /// Draw a sample.
+    fn sample(&self, source: &mut dyn Source) -> Self::Value;
+}
+
+/// A distribution of any of the built-in univariate types.
+///
+/// The outcomes of the discrete distributions are represented by `f64`. The
+/// density of a discrete distribution is understood as its probability mass
+/// function, which is zero at points outside the support.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(::serde::Deserialize, ::serde::Serialize))]
+pub enum AnyDistribution {
+    Bernoulli(Bernoulli),
+    Beta(Beta),
+    Binomial(Binomial),
+    Categorical(Categorical),
+    ChiSquared(ChiSquared),
+    Exponential(Exponential),
+    FisherSnedecor(FisherSnedecor),
+    Gamma(Gamma),
+    Gaussian(Gaussian),
+    Laplace(Laplace),
+    Logistic(Logistic),
+    Lognormal(Lognormal),
+    Pert(Pert),
+    Poisson(Poisson),
+    StudentsT(StudentsT),
+    Triangular(Triangular),
+    Uniform(Uniform),
+}
+
+// A source of randomness behind a trait object.
+struct Adapter<'l>(&'l mut dyn Source);
+
+macro_rules! continuous(
+    ($distribution:expr, $inner:ident => $body:expr) => (match *$distribution {
+        AnyDistribution::Beta(ref $inner) => $body,
+        AnyDistribution::ChiSquared(ref $inner) => $body,
+        AnyDistribution::Exponential(ref $inner) => $body,
+        AnyDistribution::FisherSnedecor(ref $inner) => $body,
+        AnyDistribution::Gamma(ref $inner) => $body,
+        AnyDistribution::Gaussian(ref $inner) => $body,
+        AnyDistribution::Laplace(ref $inner) => $body,
+        AnyDistribution::Logistic(ref $inner) => $body,
+        AnyDistribution::Lognormal(ref $inner) => $body,
+        AnyDistribution::Pert(ref $inner) => $body,
+        AnyDistribution::StudentsT(ref $inner) => $body,
+        AnyDistribution::Triangular(ref $inner) => $body,
+        AnyDistribution::Uniform(ref $inner) => $body,
+        _ => unreachable!(),
+    });
+);
+
+macro_rules! forward(
+    ($distribution:expr, $inner:ident => $body:expr) => (
+        forward!($distribution, $inner => $body, $body)
+    );
+    ($distribution:expr, $inner:ident => $discrete:expr, $continuous:expr) => (
+        match *$distribution {
+            AnyDistribution::Bernoulli(ref $inner) => $discrete,
+            AnyDistribution::Binomial(ref $inner) => $discrete,
+            AnyDistribution::Categorical(ref $inner) => $discrete,
+            AnyDistribution::Poisson(ref $inner) => $discrete,
+            _ => continuous!($distribution, $inner => $continuous),
+        }
+    );
+);
+
+macro_rules! implement(
+    ($($kind:ident),*) => ($(
+        impl From<$kind> for AnyDistribution {
+            #[inline]
+            fn from(distribution: $kind) -> Self {
+                AnyDistribution::$kind(distribution)
+            }
+        }
+    )*);
+);
+
+impl<D> DynContinuous for D
+where
+    D: Continuous<Value = f64> + Sample,
+{
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        Continuous::density(self, x)
+    }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        Continuous::ln_density(self, x)
+    }
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        Distribution::distribution(self, x)
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        Distribution::survival(self, x)
+    }
+
+    #[inline]
+    fn sample(&self, source: &mut dyn Source) -> f64 {
+        Sample::sample(self, &mut Adapter(source))
+    }
+}
+
+impl<D> DynDiscrete for D
+where
+    D: Discrete + Sample,
+{
+    type Value = D::Value;
+
+    #[inline]
+    fn mass(&self, x: Self::Value) -> f64 {
+        Discrete::mass(self, x)
+    }
+
+    #[inline]
+    fn ln_mass(&self, x: Self::Value) -> f64 {
+        Discrete::ln_mass(self, x)
+    }
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        Distribution::distribution(self, x)
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        Distribution::survival(self, x)
+    }
+
+    #[inline]
+    fn sample(&self, source: &mut dyn Source) -> Self::Value {
+        Sample::sample(self, &mut Adapter(source))
+    }
+}
+
+impl AnyDistribution {
+    /// Compute the probability density function.
+    pub fn density(&self, x: f64) -> f64 {
+        match *self {
+            AnyDistribution::Bernoulli(ref d) => {
+                outcome(x, 1).map_or(0.0, |x| Discrete::mass(d, x as u8))
+            }
+            AnyDistribution::Binomial(ref d) => {
+                outcome(x, d.n()).map_or(0.0, |x| Discrete::mass(d, x))
+            }
+            AnyDistribution::Categorical(ref d) => {
+                outcome(x, d.k() - 1).map_or(0.0, |x| Discrete::mass(d, x))
+            }
+            AnyDistribution::Poisson(ref d) => {
+                outcome(x, ::std::usize::MAX).map_or(0.0, |x| Discrete::mass(d, x))
+            }
+            _ => continuous!(self, d => Continuous::density(d, x)),
+        }
+    }
+
+    /// Compute the natural logarithm of the probability density function.
+    pub fn ln_density(&self, x: f64) -> f64 {
+        use std::f64::NEG_INFINITY;
+        match *self {
+            AnyDistribution::Bernoulli(ref d) => {
+                outcome(x, 1).map_or(NEG_INFINITY, |x| Discrete::ln_mass(d, x as u8))
+            }
+            AnyDistribution::Binomial(ref d) => {
+                outcome(x, d.n()).map_or(NEG_INFINITY, |x| Discrete::ln_mass(d, x))
+            }
+            AnyDistribution::Categorical(ref d) => {
+                outcome(x, d.k() - 1).map_or(NEG_INFINITY, |x| Discrete::ln_mass(d, x))
+            }
+            AnyDistribution::Poisson(ref d) => {
+                outcome(x, ::std::usize::MAX).map_or(NEG_INFINITY, |x| Discrete::ln_mass(d, x))
+            }
+            _ => continuous!(self, d => Continuous::ln_density(d, x)),
+        }
+    }
+
+    /// Check if the distribution is discrete.
+    pub fn is_discrete(&self) -> bool {
+        matches!(
+            *self,
+            AnyDistribution::Bernoulli(_)
+                | AnyDistribution::Binomial(_)
+                | AnyDistribution::Categorical(_)
+                | AnyDistribution::Poisson(_)
+        )
+    }
+}
+
+impl fmt::Display for AnyDistribution {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        forward!(self, d => fmt::Display::fmt(d, formatter))
+    }
+}
+
+impl Distribution for AnyDistribution {
+    type Value = f64;
+
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        forward!(self, d => Distribution::distribution(d, x))
+    }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        forward!(self, d => Distribution::ln_distribution(d, x))
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        forward!(self, d => Distribution::ln_survival(d, x))
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        forward!(self, d => Distribution::survival(d, x))
+    }
+}
+
+impl Entropy for AnyDistribution {
+    #[inline]
+    fn entropy(&self) -> f64 {
+        forward!(self, d => Entropy::entropy(d))
+    }
+}
+
+impl Inverse for AnyDistribution {
+    #[inline]
+    fn inverse(&self, p: f64) -> f64 {
+        forward!(self, d => Inverse::inverse(d, p) as f64, Inverse::inverse(d, p))
+    }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        forward!(self, d => Inverse::inverse_survival(d, q) as f64, Inverse::inverse_survival(d, q))
+    }
+}
+
+impl Kurtosis for AnyDistribution {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        forward!(self, d => Kurtosis::kurtosis(d))
+    }
+}
+
+impl Mean for AnyDistribution {
+    #[inline]
+    fn mean(&self) -> f64 {
+        forward!(self, d => Mean::mean(d))
+    }
+}
+
+impl Median for AnyDistribution {
+    #[inline]
+    fn median(&self) -> f64 {
+        forward!(self, d => Median::median(d))
+    }
+}
+
+impl Sample for AnyDistribution {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        forward!(self, d => Sample::sample(d, source) as f64, Sample::sample(d, source))
+    }
+}
+
+impl Skewness for AnyDistribution {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        forward!(self, d => Skewness::skewness(d))
+    }
+}
+
+impl Variance for AnyDistribution {
+    #[inline]
+    fn variance(&self) -> f64 {
+        forward!(self, d => Variance::variance(d))
+    }
+}
+
+implement!(
+    Bernoulli,
+    Beta,
+    Binomial,
+    Categorical,
+    ChiSquared,
+    Exponential,
+    FisherSnedecor,
+    Gamma,
+    Gaussian,
+    Laplace,
+    Logistic,
+    Lognormal,
+    Pert,
+    Poisson,
+    StudentsT,
+    Triangular,
+    Uniform
+);
+
+impl<'l> Source for Adapter<'l> {
+    #[inline(always)]
+    fn read_u64(&mut self) -> u64 {
+        self.0.read_u64()
+    }
+
+    #[inline(always)]
+    fn read_f64(&mut self) -> f64 {
+        self.0.read_f64()
+    }
+}
+
+// Convert a point into an outcome of a discrete distribution whose support is
+// bounded by `n`.
+fn outcome(x: f64, n: usize) -> Option<usize> {
+    if x >= 0.0 && x.fract() == 0.0 && x <= n as f64 {
+        Some(x as usize)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use distribution;
+    use prelude::*;
+
+    #[test]
+    fn density() {
+        let d = AnyDistribution::from(Gaussian::new(1.0, 2.0));
+        assert_eq!(d.density(0.5), Gaussian::new(1.0, 2.0).density(0.5));
+
+        let d = AnyDistribution::from(Binomial::new(4, 0.25));
+        assert_eq!(d.density(1.0), Binomial::new(4, 0.25).mass(1));
+        assert_eq!(d.density(1.5), 0.0);
+        assert_eq!(d.density(-1.0), 0.0);
+        assert_eq!(d.density(5.0), 0.0);
+
+        let d = AnyDistribution::from(Bernoulli::new(0.25));
+        assert_eq!(d.density(1.0), 0.25);
+        assert_eq!(d.density(2.0), 0.0);
+    }
+
+    #[test]
+    fn dyn_continuous() {
+        let distributions: Vec<Box<dyn distribution::DynContinuous>> = vec![
+            Box::new(Gaussian::new(0.0, 1.0)),
+            Box::new(Uniform::new(2.0, 3.0)),
+            Box::new(Exponential::new(1.0)),
+        ];
+        assert_eq!(distributions[1].density(2.5), 1.0);
+        assert_eq!(distributions[0].distribution(0.0), 0.5);
+
+        let mut source = source::default();
+        for _ in 0..100 {
+            let x = distributions[1].sample(&mut source);
+            assert!(2.0 <= x && x <= 3.0);
+        }
+    }
+
+    #[test]
+    fn dyn_discrete() {
+        let distributions: Vec<Box<dyn distribution::DynDiscrete<Value = usize>>> = vec![
+            Box::new(Binomial::new(10, 0.5)),
+            Box::new(Categorical::new(&[0.0, 0.5, 0.5])),
+            Box::new(Poisson::new(2.0)),
+        ];
+        assert_eq!(distributions[1].mass(0), 0.0);
+        assert_eq!(distributions[1].survival(1.0), 0.5);
+
+        let mut source = source::default();
+        for _ in 0..100 {
+            let x = distributions[1].sample(&mut source);
+            assert!(x == 1 || x == 2);
+        }
+    }
+
+    #[test]
+    fn inverse() {
+        let d = AnyDistribution::from(Poisson::new(2.0));
+        assert_eq!(d.inverse(0.5), Poisson::new(2.0).inverse(0.5) as f64);
+
+        let d = AnyDistribution::from(Gaussian::new(0.0, 1.0));
+        assert_eq!(d.inverse(0.5), 0.0);
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = AnyDistribution::from(Beta::new(2.0, 3.0, 0.0, 1.0));
+        assert_eq!(
+            d.ln_density(0.4),
+            Beta::new(2.0, 3.0, 0.0, 1.0).ln_density(0.4)
+        );
+
+        let d = AnyDistribution::from(Categorical::new(&[0.25, 0.75]));
+        assert::close(d.ln_density(1.0), 0.75f64.ln(), 1e-15);
+        assert_eq!(d.ln_density(2.0), ::std::f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn moments() {
+        let distributions = vec![
+            AnyDistribution::from(Gamma::new(2.0, 3.0)),
+            AnyDistribution::from(Binomial::new(16, 0.25)),
+        ];
+        assert_eq!(distributions[0].mean(), Gamma::new(2.0, 3.0).mean());
+        assert_eq!(distributions[0].variance(), Gamma::new(2.0, 3.0).variance());
+        assert_eq!(distributions[1].mean(), 4.0);
+        assert_eq!(distributions[1].variance(), 3.0);
+        assert_eq!(
+            distributions[1].skewness(),
+            Binomial::new(16, 0.25).skewness()
+        );
+        assert!(distributions[1].is_discrete());
+        assert!(!distributions[0].is_discrete());
+    }
+
+    #[test]
+    fn sample() {
+        let mut source = source::default();
+        let d = AnyDistribution::from(Bernoulli::new(0.5));
+        assert!(Independent(&d, &mut source)
+            .take(100)
+            .all(|x| x == 0.0 || x == 1.0));
+    }
+}
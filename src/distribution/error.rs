@@ -0,0 +1,186 @@
+use std::{error, fmt};
+
+/// An error in the parameters of a distribution.
+///
+/// Each variant carries the names of the offending parameters.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A collection of parameters is empty.
+    Empty(&'static str),
+    /// The dimensions of two parameters do not match.
+    Mismatch(&'static str, &'static str),
+    /// A parameter is negative.
+    Negative(&'static str),
+    /// A parameter is infinite or not a number.
+    NotFinite(&'static str),
+    /// The first parameter is not less than the second one.
+    NotLess(&'static str, &'static str),
+    /// The first parameter is greater than the second one.
+    NotLessOrEqual(&'static str, &'static str),
+    /// A collection of probabilities does not sum up to one.
+    NotNormalized(&'static str),
+    /// A parameter is not positive.
+    NotPositive(&'static str),
+    /// A matrix parameter is not positive definite.
+    NotPositiveDefinite(&'static str),
+    /// A parameter is not a probability strictly between zero and one.
+    NotProbability(&'static str),
+    /// A collection of weights sums up to zero.
+    Zero(&'static str),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Error::Empty(name) => write!(formatter, "`{}` should not be empty", name),
+            Error::Mismatch(first, second) => write!(
+                formatter,
+                "the dimensions of `{}` and `{}` should match",
+                first, second
+            ),
+            Error::Negative(name) => write!(formatter, "`{}` should not be negative", name),
+            Error::NotFinite(name) => write!(formatter, "`{}` should be finite", name),
+            Error::NotLess(first, second) => {
+                write!(formatter, "`{}` should be less than `{}`", first, second)
+            }
+            Error::NotLessOrEqual(first, second) => write!(
+                formatter,
+                "`{}` should be less than or equal to `{}`",
+                first, second
+            ),
+            Error::NotNormalized(name) => write!(formatter, "`{}` should sum up to one", name),
+            Error::NotPositive(name) => write!(formatter, "`{}` should be positive", name),
+            Error::NotPositiveDefinite(name) => {
+                write!(formatter, "`{}` should be positive definite", name)
+            }
+            Error::NotProbability(name) => {
+                write!(formatter, "`{}` should be between zero and one", name)
+            }
+            Error::Zero(name) => write!(formatter, "`{}` should not sum up to zero", name),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+/// Check that a parameter is finite.
+#[inline]
+pub fn finite(value: f64, name: &'static str) -> Result<(), Error> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(Error::NotFinite(name))
+    }
+}
+
+/// Check that one parameter is less than another.
+#[inline]
+pub fn less(first: f64, second: f64, names: (&'static str, &'static str)) -> Result<(), Error> {
+    finite(first, names.0)?;
+    finite(second, names.1)?;
+    if first < second {
+        Ok(())
+    } else {
+        Err(Error::NotLess(names.0, names.1))
+    }
+}
+
+/// Check that one parameter is less than or equal to another.
+#[inline]
+pub fn less_or_equal(
+    first: f64,
+    second: f64,
+    names: (&'static str, &'static str),
+) -> Result<(), Error> {
+    finite(first, names.0)?;
+    finite(second, names.1)?;
+    if first <= second {
+        Ok(())
+    } else {
+        Err(Error::NotLessOrEqual(names.0, names.1))
+    }
+}
+
+/// Check that a parameter is finite and not negative.
+#[inline]
+pub fn non_negative(value: f64, name: &'static str) -> Result<(), Error> {
+    finite(value, name)?;
+    if value >= 0.0 {
+        Ok(())
+    } else {
+        Err(Error::Negative(name))
+    }
+}
+
+/// Check that a parameter is finite and positive.
+#[inline]
+pub fn positive(value: f64, name: &'static str) -> Result<(), Error> {
+    finite(value, name)?;
+    if value > 0.0 {
+        Ok(())
+    } else {
+        Err(Error::NotPositive(name))
+    }
+}
+
+/// Check that a parameter is a probability strictly between zero and one.
+#[inline]
+pub fn probability(value: f64, name: &'static str) -> Result<(), Error> {
+    if value > 0.0 && value < 1.0 {
+        Ok(())
+    } else {
+        Err(Error::NotProbability(name))
+    }
+}
+
+/// Check that a collection of parameters is a probability vector.
+pub fn probability_vector(values: &[f64], name: &'static str) -> Result<(), Error> {
+    const EPSILON: f64 = 1e-12;
+    if values.is_empty() {
+        return Err(Error::Empty(name));
+    }
+    let mut sum = 0.0;
+    for &value in values {
+        finite(value, name)?;
+        if value < 0.0 {
+            return Err(Error::Negative(name));
+        }
+        sum += value;
+    }
+    if (sum - 1.0).abs() < EPSILON {
+        Ok(())
+    } else {
+        Err(Error::NotNormalized(name))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(
+            Error::NotPositive("sigma").to_string(),
+            "`sigma` should be positive"
+        );
+        assert_eq!(
+            Error::NotLess("a", "b").to_string(),
+            "`a` should be less than `b`"
+        );
+    }
+
+    #[test]
+    fn probability_vector() {
+        assert_eq!(super::probability_vector(&[0.25, 0.75], "p"), Ok(()));
+        assert_eq!(super::probability_vector(&[], "p"), Err(Error::Empty("p")));
+        assert_eq!(
+            super::probability_vector(&[-0.5, 1.5], "p"),
+            Err(Error::Negative("p"))
+        );
+        assert_eq!(
+            super::probability_vector(&[0.5, 0.25], "p"),
+            Err(Error::NotNormalized("p"))
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// An exponential distribution.
@@ -17,6 +17,14 @@ impl Exponential {
         Exponential { lambda: lambda }
     }
 
+    /// Create an exponential distribution with rate `lambda` or fail if the
+    /// parameter is invalid.
+    #[inline]
+    pub fn try_new(lambda: f64) -> Result<Self, Error> {
+        error::positive(lambda, "lambda")?;
+        Ok(Exponential::new(lambda))
+    }
+
     /// Return the rate parameter.
     #[inline(always)]
     pub fn lambda(&self) -> f64 {
@@ -33,6 +41,15 @@ impl distribution::Continuous for Exponential {
             self.lambda * (-self.lambda * x).exp()
         }
     }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            ::std::f64::NEG_INFINITY
+        } else {
+            self.lambda.ln() - self.lambda * x
+        }
+    }
 }
 
 impl distribution::Distribution for Exponential {
@@ -46,6 +63,33 @@ impl distribution::Distribution for Exponential {
             -(-self.lambda * x).exp_m1()
         }
     }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            ::std::f64::NEG_INFINITY
+        } else {
+            (-(-self.lambda * x).exp_m1()).ln()
+        }
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            -self.lambda * x
+        }
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            (-self.lambda * x).exp()
+        }
+    }
 }
 
 impl distribution::Entropy for Exponential {
@@ -55,12 +99,42 @@ impl distribution::Entropy for Exponential {
     }
 }
 
+impl distribution::Fit for Exponential {
+    fn fit(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        fit::check(data, 1)?;
+        if data.iter().any(|&x| x < 0.0) {
+            return Err(FitError::OutOfSupport);
+        }
+        let mean = fit::mean(data.iter().cloned());
+        Ok(fit::continuous(
+            Exponential::try_new(1.0 / mean)?,
+            data,
+            0,
+            true,
+        ))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    ///
+    /// The estimate coincides with the maximum-likelihood one.
+    #[inline]
+    fn fit_moments(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        Self::fit(data)
+    }
+}
+
 impl distribution::Inverse for Exponential {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
         should!(0.0 <= p && p <= 1.0);
         -(-p).ln_1p() / self.lambda
     }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        should!(0.0 <= q && q <= 1.0);
+        -q.ln() / self.lambda
+    }
 }
 
 impl distribution::Kurtosis for Exponential {
@@ -190,6 +264,37 @@ mod tests {
         assert_eq!(new!(E).entropy(), 0.0);
     }
 
+    #[test]
+    fn fit() {
+        use distribution::FitError;
+
+        let data = vec![0.5, 1.2, 2.0, 2.7, 3.1, 4.4, 5.9, 8.3];
+        let estimate = Exponential::fit(&data).unwrap();
+        assert::close(
+            estimate.distribution.lambda(),
+            2.8469750889679718e-01,
+            1e-15,
+        );
+        assert::close(estimate.ln_likelihood, -1.8050624277278914e+01, 1e-13);
+
+        assert_eq!(Exponential::fit(&[]).unwrap_err(), FitError::Insufficient);
+        assert_eq!(
+            Exponential::fit(&[1.0, -1.0]).unwrap_err(),
+            FitError::OutOfSupport
+        );
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![0.5, 1.2, 2.0, 2.7, 3.1, 4.4, 5.9, 8.3];
+        let estimate = Exponential::fit_moments(&data).unwrap();
+        assert::close(
+            estimate.distribution.lambda(),
+            2.8469750889679718e-01,
+            1e-15,
+        );
+    }
+
     #[test]
     fn inverse() {
         use std::f64::INFINITY;
@@ -221,11 +326,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(2.0);
+        for &q in &[0.001, 0.1, 0.5, 0.9, 0.999] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-12);
+        }
+        assert::close(d.inverse_survival(1e-300), 300.0 * 10f64.ln() / 2.0, 1e-12);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(2.0).kurtosis(), 6.0);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(2.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 5.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+        assert_eq!(d.ln_density(1e3), 2f64.ln() - 2e3);
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(2.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 5.0] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+        assert::close(d.ln_distribution(1e-20), 2e-20f64.ln(), 1e-14);
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(2.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-14);
+        }
+        assert_eq!(d.ln_survival(1e3), -2e3);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(2.0).mean(), 0.5);
@@ -247,6 +388,26 @@ mod tests {
         assert_eq!(new!(2.0).skewness(), 2.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(2.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 5.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-15);
+        }
+        assert_eq!(d.survival(100.0), (-200f64).exp());
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Exponential::try_new(2.0).is_ok());
+        assert_eq!(
+            Exponential::try_new(0.0).unwrap_err(),
+            Error::NotPositive("lambda")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(2.0).variance(), 0.25);
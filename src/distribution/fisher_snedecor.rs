@@ -0,0 +1,435 @@
+use distribution::{self, error, Error};
+use source::Source;
+
+/// A Fisher–Snedecor distribution.
+#[derive(Clone, Copy, Debug)]
+pub struct FisherSnedecor {
+    d1: f64,
+    d2: f64,
+    ln_beta: f64,
+}
+
+impl FisherSnedecor {
+    /// Create a Fisher–Snedecor distribution with `d1` and `d2` degrees of
+    /// freedom.
+    ///
+    /// It should hold that `d1 > 0` and `d2 > 0`.
+    #[inline]
+    pub fn new(d1: f64, d2: f64) -> Self {
+        use special::Beta;
+        should!(d1 > 0.0 && d2 > 0.0);
+        FisherSnedecor {
+            d1: d1,
+            d2: d2,
+            ln_beta: (0.5 * d1).ln_beta(0.5 * d2),
+        }
+    }
+
+    /// Create a Fisher–Snedecor distribution with `d1` and `d2` degrees of
+    /// freedom or fail if the parameters are invalid.
+    #[inline]
+    pub fn try_new(d1: f64, d2: f64) -> Result<Self, Error> {
+        error::positive(d1, "d1")?;
+        error::positive(d2, "d2")?;
+        Ok(FisherSnedecor::new(d1, d2))
+    }
+
+    /// Return the first degrees of freedom.
+    #[inline(always)]
+    pub fn d1(&self) -> f64 {
+        self.d1
+    }
+
+    /// Return the second degrees of freedom.
+    #[inline(always)]
+    pub fn d2(&self) -> f64 {
+        self.d2
+    }
+}
+
+impl distribution::Continuous for FisherSnedecor {
+    #[inline]
+    fn density(&self, x: f64) -> f64 {
+        self.ln_density(x).exp()
+    }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            ::std::f64::NEG_INFINITY
+        } else {
+            let &FisherSnedecor { d1, d2, ln_beta } = self;
+            0.5 * d1 * (d1 * x).ln() + 0.5 * d2 * d2.ln()
+                - 0.5 * (d1 + d2) * (d1 * x + d2).ln()
+                - x.ln()
+                - ln_beta
+        }
+    }
+}
+
+impl distribution::Distribution for FisherSnedecor {
+    type Value = f64;
+
+    /// Compute the cumulative distribution function.
+    ///
+    /// The implementation is based on the incomplete beta function.
+    fn distribution(&self, x: f64) -> f64 {
+        use special::Beta;
+        if x <= 0.0 {
+            return 0.0;
+        }
+        let &FisherSnedecor { d1, d2, ln_beta } = self;
+        let y = d1 * x / (d1 * x + d2);
+        if y < 0.5 {
+            y.inc_beta(0.5 * d1, 0.5 * d2, ln_beta)
+        } else {
+            1.0 - (d2 / (d1 * x + d2)).inc_beta(0.5 * d2, 0.5 * d1, ln_beta)
+        }
+    }
+
+    fn ln_distribution(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            return ::std::f64::NEG_INFINITY;
+        }
+        self.distribution(x).ln()
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        use special::Beta;
+        if x <= 0.0 {
+            return 1.0;
+        }
+        let &FisherSnedecor { d1, d2, ln_beta } = self;
+        let y = d1 * x / (d1 * x + d2);
+        if y < 0.5 {
+            1.0 - y.inc_beta(0.5 * d1, 0.5 * d2, ln_beta)
+        } else {
+            (d2 / (d1 * x + d2)).inc_beta(0.5 * d2, 0.5 * d1, ln_beta)
+        }
+    }
+}
+
+impl distribution::Entropy for FisherSnedecor {
+    fn entropy(&self) -> f64 {
+        use special::Gamma;
+        let &FisherSnedecor { d1, d2, ln_beta } = self;
+        ln_beta + (d2 / d1).ln() + (1.0 - 0.5 * d1) * (0.5 * d1).digamma()
+            - (1.0 + 0.5 * d2) * (0.5 * d2).digamma()
+            + 0.5 * (d1 + d2) * (0.5 * (d1 + d2)).digamma()
+    }
+}
+
+impl distribution::Inverse for FisherSnedecor {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The implementation is based on the inverse of the incomplete beta
+    /// function.
+    fn inverse(&self, p: f64) -> f64 {
+        use special::Beta;
+        should!(0.0 <= p && p <= 1.0);
+        if p >= 1.0 {
+            return ::std::f64::INFINITY;
+        }
+        let y = p.inv_inc_beta(0.5 * self.d1, 0.5 * self.d2, self.ln_beta);
+        self.d2 * y / (self.d1 * (1.0 - y))
+    }
+
+    fn inverse_survival(&self, q: f64) -> f64 {
+        use special::Beta;
+        should!(0.0 <= q && q <= 1.0);
+        if q <= 0.0 {
+            return ::std::f64::INFINITY;
+        }
+        let z = q.inv_inc_beta(0.5 * self.d2, 0.5 * self.d1, self.ln_beta);
+        self.d2 * (1.0 - z) / (self.d1 * z)
+    }
+}
+
+impl distribution::Kurtosis for FisherSnedecor {
+    /// Compute the excess kurtosis.
+    ///
+    /// The kurtosis is undefined if `d2 <= 8`.
+    fn kurtosis(&self) -> f64 {
+        let &FisherSnedecor { d1, d2, .. } = self;
+        if d2 > 8.0 {
+            12.0 * (d1 * (5.0 * d2 - 22.0) * (d1 + d2 - 2.0) + (d2 - 4.0) * (d2 - 2.0).powi(2))
+                / (d1 * (d2 - 6.0) * (d2 - 8.0) * (d1 + d2 - 2.0))
+        } else {
+            ::std::f64::NAN
+        }
+    }
+}
+
+impl distribution::Mean for FisherSnedecor {
+    /// Compute the expected value.
+    ///
+    /// The expected value is infinite if `d2 <= 2`.
+    #[inline]
+    fn mean(&self) -> f64 {
+        if self.d2 > 2.0 {
+            self.d2 / (self.d2 - 2.0)
+        } else {
+            ::std::f64::INFINITY
+        }
+    }
+}
+
+impl distribution::Median for FisherSnedecor {
+    fn median(&self) -> f64 {
+        use distribution::Inverse;
+        self.inverse(0.5)
+    }
+}
+
+impl distribution::Modes for FisherSnedecor {
+    fn modes(&self) -> Vec<f64> {
+        let &FisherSnedecor { d1, d2, .. } = self;
+        if d1 >= 2.0 {
+            vec![(d1 - 2.0) / d1 * d2 / (d2 + 2.0)]
+        } else {
+            vec![]
+        }
+    }
+}
+
+impl distribution::Sample for FisherSnedecor {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        use distribution::gamma;
+        let x = gamma::sample(0.5 * self.d1, source) / self.d1;
+        let y = gamma::sample(0.5 * self.d2, source) / self.d2;
+        x / y
+    }
+}
+
+impl distribution::Skewness for FisherSnedecor {
+    /// Compute the skewness.
+    ///
+    /// The skewness is undefined if `d2 <= 6`.
+    fn skewness(&self) -> f64 {
+        let &FisherSnedecor { d1, d2, .. } = self;
+        if d2 > 6.0 {
+            (2.0 * d1 + d2 - 2.0) * (8.0 * (d2 - 4.0)).sqrt()
+                / ((d2 - 6.0) * (d1 * (d1 + d2 - 2.0)).sqrt())
+        } else {
+            ::std::f64::NAN
+        }
+    }
+}
+
+impl distribution::Variance for FisherSnedecor {
+    /// Compute the variance.
+    ///
+    /// The variance is infinite if `d2 <= 4` and undefined if `d2 <= 2`.
+    fn variance(&self) -> f64 {
+        use std::f64::{INFINITY, NAN};
+        let &FisherSnedecor { d1, d2, .. } = self;
+        if d2 > 4.0 {
+            2.0 * d2 * d2 * (d1 + d2 - 2.0) / (d1 * (d2 - 2.0).powi(2) * (d2 - 4.0))
+        } else if d2 > 2.0 {
+            INFINITY
+        } else {
+            NAN
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($d1:expr, $d2:expr) => (FisherSnedecor::new($d1, $d2));
+    );
+
+    #[test]
+    fn density() {
+        let d = new!(4.0, 9.0);
+        let x = vec![-1.0, 0.0, 0.1, 0.25, 0.5, 1.0, 1.5, 2.0, 3.0, 5.0, 10.0, 30.0];
+        let p = vec![
+            0.0000000000000000e+00,
+            0.0000000000000000e+00,
+            3.6851522059233238e-01,
+            6.1620680073243272e-01,
+            6.6329088497329536e-01,
+            4.4787280054703521e-01,
+            2.6502360280095810e-01,
+            1.5663848900489491e-01,
+            5.9495253229604091e-02,
+            1.2166607462001386e-02,
+            8.0447389928516271e-04,
+            4.4676034349535285e-06,
+        ];
+
+        assert::close(
+            &x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(),
+            &p,
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(4.0, 9.0);
+        let x = vec![-1.0, 0.0, 0.1, 0.25, 0.5, 1.0, 1.5, 2.0, 3.0, 5.0, 10.0, 30.0];
+        let p = vec![
+            0.0000000000000000e+00,
+            0.0000000000000000e+00,
+            2.0270645859576972e-02,
+            9.7474887816133948e-02,
+            2.6301012780744965e-01,
+            5.4421025600895157e-01,
+            7.1891436066565051e-01,
+            8.2180394116993649e-01,
+            9.2112371730923703e-01,
+            9.7879446345890564e-01,
+            9.9772045413538413e-01,
+            9.9996754556868350e-01,
+        ];
+
+        assert::close(
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            &p,
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn entropy() {
+        assert::close(new!(4.0, 9.0).entropy(), 1.1944539447253175, 1e-14);
+    }
+
+    #[test]
+    fn inverse() {
+        use std::f64::INFINITY;
+
+        let d = new!(4.0, 9.0);
+        let p = vec![
+            0.0, 0.001, 0.05, 0.1, 0.25, 0.5, 0.75, 0.9, 0.95, 0.999, 1.0,
+        ];
+        let x = vec![
+            0.0000000000000000e+00,
+            2.0629398259095426e-02,
+            1.6670058936947546e-01,
+            2.5408629100273039e-01,
+            4.8045110831387250e-01,
+            9.0580385439682520e-01,
+            1.6252977982628942e+00,
+            2.6926800625023413e+00,
+            3.6330885114190812e+00,
+            1.2560318739590738e+01,
+            INFINITY,
+        ];
+
+        assert::close(
+            &p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
+            &x,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn inverse_survival() {
+        let d = new!(4.0, 9.0);
+        for &q in &[0.001, 0.1, 0.5, 0.9, 0.999] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-10);
+        }
+        assert::close(
+            d.inverse_survival(1e-10) / 5.4552839244900101e+02,
+            1.0,
+            1e-10,
+        );
+    }
+
+    #[test]
+    fn kurtosis() {
+        assert::close(new!(5.0, 12.0).kurtosis(), 24.333333333333332, 1e-13);
+        assert!(new!(5.0, 8.0).kurtosis().is_nan());
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = new!(4.0, 9.0);
+        for &x in &[-1.0, 0.0, 0.1, 1.0, 5.0, 30.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(4.0, 9.0);
+        for &x in &[-1.0, 0.0, 0.1, 1.0, 5.0, 30.0] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(4.0, 9.0);
+        for &x in &[-1.0, 0.0, 0.1, 1.0, 5.0, 30.0] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-12);
+        }
+        assert::close(d.ln_survival(1e6), -5.6815875411522582e+01, 1e-12);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!(4.0, 9.0).mean(), 9.0 / 7.0);
+        assert!(new!(4.0, 2.0).mean().is_infinite());
+    }
+
+    #[test]
+    fn median() {
+        assert::close(new!(4.0, 9.0).median(), 9.0580385439682520e-01, 1e-14);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(new!(4.0, 9.0).modes(), vec![0.5 * 9.0 / 11.0]);
+        assert_eq!(new!(1.0, 9.0).modes(), vec![]);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!(4.0, 9.0);
+        for x in Independent(&d, &mut source::default()).take(100) {
+            assert!(x > 0.0);
+        }
+    }
+
+    #[test]
+    fn skewness() {
+        assert::close(new!(5.0, 12.0).skewness(), 3.079201435678004, 1e-14);
+        assert!(new!(5.0, 6.0).skewness().is_nan());
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!(4.0, 9.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 2.5, 10.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-14);
+        }
+        assert::close(d.survival(1e6) / 2.1143594652697416e-25, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(FisherSnedecor::try_new(4.0, 9.0).is_ok());
+        assert_eq!(
+            FisherSnedecor::try_new(4.0, ::std::f64::NAN).unwrap_err(),
+            Error::NotFinite("d2")
+        );
+    }
+
+    #[test]
+    fn variance() {
+        assert::close(new!(5.0, 12.0).variance(), 1.08, 1e-14);
+        assert!(new!(5.0, 4.0).variance().is_infinite());
+        assert!(new!(5.0, 2.0).variance().is_nan());
+    }
+}
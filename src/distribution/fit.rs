@@ -0,0 +1,202 @@
+use std::{error, fmt};
+
+use distribution::{self, Continuous, Discrete};
+
+/// The result of estimating the parameters of a distribution.
+#[derive(Clone, Debug)]
+pub struct Estimate<D> {
+    /// The fitted distribution.
+    pub distribution: D,
+    /// The log-likelihood of the data under the fitted distribution.
+    pub ln_likelihood: f64,
+    /// The number of iterations taken by the solver, which is zero for
+    /// closed-form estimates.
+    pub iterations: usize,
+    /// The indicator of the solver having reached the requested tolerance.
+    pub converged: bool,
+}
+
+/// An error in estimating the parameters of a distribution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FitError {
+    /// The data do not determine the parameters, for instance, when all the
+    /// observations are identical.
+    Degenerate,
+    /// The data contain too few observations.
+    Insufficient,
+    /// An observation is infinite or not a number.
+    NotFinite,
+    /// An observation lies outside the support of the distribution.
+    OutOfSupport,
+    /// The estimated parameters are invalid.
+    Parameter(distribution::Error),
+}
+
+impl fmt::Display for FitError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            FitError::Degenerate => write!(formatter, "the data are degenerate"),
+            FitError::Insufficient => write!(formatter, "the data are insufficient"),
+            FitError::NotFinite => write!(formatter, "the data should be finite"),
+            FitError::OutOfSupport => write!(formatter, "the data are out of support"),
+            FitError::Parameter(ref error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+impl error::Error for FitError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            FitError::Parameter(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<distribution::Error> for FitError {
+    #[inline]
+    fn from(error: distribution::Error) -> Self {
+        FitError::Parameter(error)
+    }
+}
+
+/// The maximal number of iterations taken by the iterative solvers.
+pub const ITERATIONS: usize = 100;
+
+/// The relative tolerance of the iterative solvers.
+pub const TOLERANCE: f64 = 1e-12;
+
+/// Check that the data contain at least `count` finite observations.
+pub fn check(data: &[f64], count: usize) -> Result<(), FitError> {
+    if data.len() < count {
+        return Err(FitError::Insufficient);
+    }
+    if data.iter().any(|x| !x.is_finite()) {
+        return Err(FitError::NotFinite);
+    }
+    Ok(())
+}
+
+/// Assemble an estimate for a continuous distribution.
+pub fn continuous<D>(
+    distribution: D,
+    data: &[f64],
+    iterations: usize,
+    converged: bool,
+) -> Estimate<D>
+where
+    D: Continuous<Value = f64>,
+{
+    let ln_likelihood = data
+        .iter()
+        .fold(0.0, |sum, &x| sum + distribution.ln_density(x));
+    Estimate {
+        distribution: distribution,
+        ln_likelihood: ln_likelihood,
+        iterations: iterations,
+        converged: converged,
+    }
+}
+
+/// Assemble an estimate for a discrete distribution.
+pub fn discrete<D, T>(
+    distribution: D,
+    data: &[T],
+    iterations: usize,
+    converged: bool,
+) -> Estimate<D>
+where
+    D: Discrete<Value = T>,
+    T: Copy,
+{
+    let ln_likelihood = data
+        .iter()
+        .fold(0.0, |sum, &x| sum + distribution.ln_mass(x));
+    Estimate {
+        distribution: distribution,
+        ln_likelihood: ln_likelihood,
+        iterations: iterations,
+        converged: converged,
+    }
+}
+
+/// Compute the sample mean.
+pub fn mean<I>(data: I) -> f64
+where
+    I: IntoIterator<Item = f64>,
+{
+    let (count, sum) = data
+        .into_iter()
+        .fold((0usize, 0.0), |(count, sum), x| (count + 1, sum + x));
+    sum / count as f64
+}
+
+/// Compute the sample median.
+pub fn median(data: &[f64]) -> f64 {
+    let mut data = data.to_vec();
+    data.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = data.len();
+    if n % 2 == 1 {
+        data[n / 2]
+    } else {
+        0.5 * (data[n / 2 - 1] + data[n / 2])
+    }
+}
+
+/// Compute the trigamma function, that is, the derivative of the digamma
+/// function.
+///
+/// The argument is shifted by the recurrence relation until it is large
+/// enough for the asymptotic expansion to be accurate.
+pub fn trigamma(mut x: f64) -> f64 {
+    let mut sum = 0.0;
+    while x < 10.0 {
+        sum += 1.0 / (x * x);
+        x += 1.0;
+    }
+    let y = 1.0 / (x * x);
+    let series = 1.0 / 6.0
+        - y * (1.0 / 30.0
+            - y * (1.0 / 42.0 - y * (1.0 / 30.0 - y * (5.0 / 66.0 - y * (691.0 / 2730.0)))));
+    sum + 1.0 / x + 0.5 * y + y / x * series
+}
+
+/// Compute the biased sample variance given the sample mean.
+pub fn variance<I>(data: I, mean: f64) -> f64
+where
+    I: IntoIterator<Item = f64>,
+{
+    let (count, sum) = data.into_iter().fold((0usize, 0.0), |(count, sum), x| {
+        (count + 1, sum + (x - mean) * (x - mean))
+    });
+    sum / count as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+
+    #[test]
+    fn median() {
+        assert_eq!(super::median(&[3.0, 1.0, 2.0]), 2.0);
+        assert_eq!(super::median(&[4.0, 1.0, 3.0, 2.0]), 2.5);
+    }
+
+    #[test]
+    fn trigamma() {
+        let x = vec![0.1, 0.5, 1.0, 2.5, 10.0, 100.0];
+        let y = vec![
+            1.0143329915079275e+02,
+            4.9348022005446790e+00,
+            1.6449340668482264e+00,
+            4.9035775610023485e-01,
+            1.0516633568168575e-01,
+            1.0050166663333571e-02,
+        ];
+        assert::close(
+            &x.iter().map(|&x| super::trigamma(x)).collect::<Vec<_>>(),
+            &y,
+            1e-12,
+        );
+    }
+}
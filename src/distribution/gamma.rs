@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// A gamma distribution.
@@ -7,6 +7,7 @@ pub struct Gamma {
     k: f64,
     theta: f64,
     norm: f64,
+    ln_norm: f64,
 }
 
 impl Gamma {
@@ -22,9 +23,19 @@ impl Gamma {
             k: k,
             theta: theta,
             norm: k.gamma() * theta.powf(k),
+            ln_norm: k.ln_gamma().0 + k * theta.ln(),
         }
     }
 
+    /// Create a gamma distribution with shape parameter `k` and scale parameter
+    /// `theta` or fail if the parameters are invalid.
+    #[inline]
+    pub fn try_new(k: f64, theta: f64) -> Result<Self, Error> {
+        error::positive(k, "k")?;
+        error::positive(theta, "theta")?;
+        Ok(Gamma::new(k, theta))
+    }
+
     /// Return the shape parameter.
     #[inline(always)]
     pub fn k(&self) -> f64 {
@@ -46,6 +57,14 @@ impl distribution::Continuous for Gamma {
             x.powf(self.k - 1.0) * (-x / self.theta).exp() / self.norm
         }
     }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            ::std::f64::NEG_INFINITY
+        } else {
+            (self.k - 1.0) * x.ln() - x / self.theta - self.ln_norm
+        }
+    }
 }
 
 impl distribution::Distribution for Gamma {
@@ -59,6 +78,21 @@ impl distribution::Distribution for Gamma {
             (x / self.theta).inc_gamma(self.k)
         }
     }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        ln_distribution(x / self.theta, self.k)
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        ln_survival(x / self.theta, self.k)
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        survival(x / self.theta, self.k)
+    }
 }
 
 impl distribution::Entropy for Gamma {
@@ -68,6 +102,98 @@ impl distribution::Entropy for Gamma {
     }
 }
 
+impl distribution::Fit for Gamma {
+    /// Estimate the parameters by the method of maximum likelihood.
+    ///
+    /// The likelihood equation for the shape parameter is solved by the
+    /// Newton method starting from an approximate closed-form solution.
+    ///
+    /// ## References
+    ///
+    /// 1. T. P. Minka, “Estimating a Gamma distribution,” Microsoft Research,
+    ///    2002.
+    fn fit(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        use special::Gamma as SpecialGamma;
+        fit::check(data, 2)?;
+        if data.iter().any(|&x| x <= 0.0) {
+            return Err(FitError::OutOfSupport);
+        }
+        let mean = fit::mean(data.iter().cloned());
+        let s = mean.ln() - fit::mean(data.iter().map(|x| x.ln()));
+        if s <= 0.0 {
+            return Err(FitError::Degenerate);
+        }
+        let mut k = (3.0 - s + ((s - 3.0) * (s - 3.0) + 24.0 * s).sqrt()) / (12.0 * s);
+        let mut converged = false;
+        let mut iterations = 0;
+        while iterations < fit::ITERATIONS {
+            iterations += 1;
+            let value = k.ln() - k.digamma() - s;
+            let derivative = 1.0 / k - fit::trigamma(k);
+            let mut step = value / derivative;
+            if !step.is_finite() {
+                break;
+            }
+            if k - step <= 0.0 {
+                step = 0.5 * k;
+            }
+            k -= step;
+            if step.abs() < fit::TOLERANCE * k {
+                converged = true;
+                break;
+            }
+        }
+        Ok(fit::continuous(
+            Gamma::try_new(k, mean / k)?,
+            data,
+            iterations,
+            converged,
+        ))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    fn fit_moments(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        fit::check(data, 2)?;
+        if data.iter().any(|&x| x <= 0.0) {
+            return Err(FitError::OutOfSupport);
+        }
+        let mean = fit::mean(data.iter().cloned());
+        let variance = fit::variance(data.iter().cloned(), mean);
+        if variance == 0.0 {
+            return Err(FitError::Degenerate);
+        }
+        Ok(fit::continuous(
+            Gamma::try_new(mean * mean / variance, variance / mean)?,
+            data,
+            0,
+            true,
+        ))
+    }
+}
+
+impl distribution::Gradient for Gamma {
+    #[inline]
+    fn gradient(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            (self.k - 1.0) / x - 1.0 / self.theta
+        }
+    }
+}
+
+impl distribution::Inverse for Gamma {
+    #[inline]
+    fn inverse(&self, p: f64) -> f64 {
+        self.theta * inverse(p, self.k)
+    }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        self.theta * inverse_survival(q, self.k)
+    }
+}
+
 impl distribution::Kurtosis for Gamma {
     #[inline]
     fn kurtosis(&self) -> f64 {
@@ -82,6 +208,13 @@ impl distribution::Mean for Gamma {
     }
 }
 
+impl distribution::Median for Gamma {
+    #[inline]
+    fn median(&self) -> f64 {
+        self.theta * inverse(0.5, self.k)
+    }
+}
+
 impl distribution::Modes for Gamma {
     fn modes(&self) -> Vec<f64> {
         if self.k >= 1.0 {
@@ -123,6 +256,69 @@ impl distribution::Variance for Gamma {
     }
 }
 
+/// Compute the cumulative distribution function of the standard Gamma
+/// distribution, that is, the lower regularized incomplete gamma function.
+///
+/// Unlike the generic implementation, the function does not underflow
+/// prematurely deep in the lower tail.
+pub fn distribution(x: f64, k: f64) -> f64 {
+    if x <= 0.0 {
+        0.0
+    } else if x < k + 1.0 {
+        series(x, k).exp()
+    } else {
+        1.0 - fraction(x, k).exp()
+    }
+}
+
+/// Compute the inverse cumulative distribution function of the standard
+/// Gamma distribution.
+///
+/// An initial approximation is refined by the Newton method applied to the
+/// logarithm of the lower or upper regularized incomplete gamma function,
+/// whichever corresponds to the shorter tail. This keeps the result accurate
+/// deep in both tails.
+///
+/// ## References
+///
+/// 1. W. H. Press, S. A. Teukolsky, W. T. Vetterling, and B. P. Flannery,
+///    “Numerical Recipes: The Art of Scientific Computing,” 3rd ed., Cambridge
+///    University Press, 2007, section 6.2.1.
+#[inline]
+pub fn inverse(p: f64, k: f64) -> f64 {
+    invert(p, 1.0 - p, k)
+}
+
+/// Compute the inverse survival function of the standard Gamma distribution.
+#[inline]
+pub fn inverse_survival(q: f64, k: f64) -> f64 {
+    invert(1.0 - q, q, k)
+}
+
+/// Compute the natural logarithm of the cumulative distribution function of
+/// the standard Gamma distribution.
+pub fn ln_distribution(x: f64, k: f64) -> f64 {
+    if x <= 0.0 {
+        ::std::f64::NEG_INFINITY
+    } else if x < k + 1.0 {
+        series(x, k)
+    } else {
+        (-fraction(x, k).exp()).ln_1p()
+    }
+}
+
+/// Compute the natural logarithm of the survival function of the standard
+/// Gamma distribution.
+pub fn ln_survival(x: f64, k: f64) -> f64 {
+    if x <= 0.0 {
+        0.0
+    } else if x < k + 1.0 {
+        (-series(x, k).exp()).ln_1p()
+    } else {
+        fraction(x, k)
+    }
+}
+
 /// Draw a sample from the standard Gamma distribution.
 pub fn sample<S: Source>(k: f64, source: &mut S) -> f64 {
     use distribution::gaussian;
@@ -162,6 +358,160 @@ pub fn sample<S: Source>(k: f64, source: &mut S) -> f64 {
     }
 }
 
+/// Compute the survival function of the standard Gamma distribution, that is,
+/// the upper regularized incomplete gamma function.
+pub fn survival(x: f64, k: f64) -> f64 {
+    if x <= 0.0 {
+        1.0
+    } else if x < k + 1.0 {
+        1.0 - series(x, k).exp()
+    } else {
+        fraction(x, k).exp()
+    }
+}
+
+// Compute the logarithm of the upper regularized incomplete gamma function
+// using its continued fraction representation, which converges quickly for
+// `x > k + 1`. The fraction is evaluated by the modified Lentz method.
+//
+// W. H. Press et al., “Numerical Recipes,” 3rd ed., 2007, section 6.2.
+fn fraction(x: f64, k: f64) -> f64 {
+    use special::Gamma;
+
+    const EPSILON: f64 = 1e-16;
+    const TINY: f64 = 1e-300;
+
+    let mut b = x + 1.0 - k;
+    let mut c = 1.0 / TINY;
+    let mut d = 1.0 / b;
+    let mut h = d;
+    for i in 1..100_000 {
+        let a = -(i as f64) * (i as f64 - k);
+        b += 2.0;
+        d = a * d + b;
+        if d.abs() < TINY {
+            d = TINY;
+        }
+        c = b + a / c;
+        if c.abs() < TINY {
+            c = TINY;
+        }
+        d = 1.0 / d;
+        let delta = d * c;
+        h *= delta;
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+    k * x.ln() - x - k.ln_gamma().0 + h.ln()
+}
+
+fn invert(p: f64, q: f64, k: f64) -> f64 {
+    use distribution::gaussian;
+    use special::Gamma;
+    use std::f64::INFINITY;
+
+    const EPSILON: f64 = 1e-8;
+
+    should!(0.0 <= p && p <= 1.0 && 0.0 <= q && q <= 1.0);
+
+    if p <= 0.0 {
+        return 0.0;
+    }
+    if q <= 0.0 {
+        return INFINITY;
+    }
+
+    let ln_gamma = k.ln_gamma().0;
+    let lower = p <= q;
+
+    let mut x = if k > 1.0 {
+        // Use the Wilson–Hilferty approximation.
+        let z = if lower {
+            gaussian::inverse(p)
+        } else {
+            -gaussian::inverse(q)
+        };
+        let w = 1.0 - 1.0 / (9.0 * k) + z / (3.0 * k.sqrt());
+        if w > 0.0 {
+            k * w * w * w
+        } else {
+            0.0
+        }
+    } else {
+        let t = 1.0 - k * (0.253 + k * 0.12);
+        if p < t {
+            (p / t).powf(1.0 / k)
+        } else {
+            1.0 - (q / (1.0 - t)).ln()
+        }
+    };
+
+    if lower {
+        // The leading term of the series expansion gives a lower bound.
+        let bound = ((p.ln() + (k + 1.0).ln_gamma().0) / k).exp();
+        if x < bound {
+            x = bound;
+        }
+    } else if survival(x, k) == 0.0 {
+        // Use the leading term of the asymptotic expansion.
+        x = (-q.ln() - ln_gamma).max(1.0);
+        for _ in 0..8 {
+            x = (-q.ln() - ln_gamma + (k - 1.0) * x.ln()).max(1.0);
+        }
+    }
+
+    for _ in 0..64 {
+        let density = ((k - 1.0) * x.ln() - x - ln_gamma).exp();
+        let value = if lower {
+            distribution(x, k)
+        } else {
+            survival(x, k)
+        };
+        if density == 0.0 || value == 0.0 {
+            break;
+        }
+        let step = if lower {
+            (value.ln() - p.ln()) * value / density
+        } else {
+            (q.ln() - value.ln()) * value / density
+        };
+        let previous = x;
+        x -= step;
+        if x <= 0.0 {
+            x = 0.5 * previous;
+        }
+        if step.abs() < EPSILON * x {
+            break;
+        }
+    }
+
+    x
+}
+
+// Compute the logarithm of the lower regularized incomplete gamma function
+// using its series representation, which converges quickly for `x < k + 1`.
+//
+// W. H. Press et al., “Numerical Recipes,” 3rd ed., 2007, section 6.2.
+fn series(x: f64, k: f64) -> f64 {
+    use special::Gamma;
+
+    const EPSILON: f64 = 1e-16;
+
+    let mut term = 1.0 / k;
+    let mut sum = term;
+    let mut i = 1.0;
+    loop {
+        term *= x / (k + i);
+        sum += term;
+        if term < EPSILON * sum {
+            break;
+        }
+        i += 1.0;
+    }
+    k * x.ln() - x - k.ln_gamma().0 + sum.ln()
+}
+
 #[cfg(test)]
 mod tests {
     use assert;
@@ -255,16 +605,202 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fit() {
+        use distribution::FitError;
+
+        let data = vec![0.5, 1.2, 2.0, 2.7, 3.1, 4.4, 5.9, 8.3];
+        let estimate = Gamma::fit(&data).unwrap();
+        assert::close(estimate.distribution.k(), 1.8362656011914458e+00, 1e-12);
+        assert::close(estimate.distribution.theta(), 1.9128496431676023e+00, 1e-12);
+        assert::close(estimate.ln_likelihood, -1.7313566792805528e+01, 1e-12);
+        assert!(estimate.converged);
+        assert!(estimate.iterations > 0);
+
+        assert_eq!(Gamma::fit(&[2.0, 2.0]).unwrap_err(), FitError::Degenerate);
+        assert_eq!(Gamma::fit(&[2.0, 0.0]).unwrap_err(), FitError::OutOfSupport);
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![0.5, 1.2, 2.0, 2.7, 3.1, 4.4, 5.9, 8.3];
+        let estimate = Gamma::fit_moments(&data).unwrap();
+        assert::close(estimate.distribution.k(), 2.1023190180782234e+00, 1e-14);
+        assert::close(estimate.distribution.theta(), 1.6707740213523132e+00, 1e-14);
+        assert::close(estimate.ln_likelihood, -1.7358239181695531e+01, 1e-13);
+    }
+
+    #[test]
+    fn gradient() {
+        let d = new!(9.0, 0.5);
+        let x = vec![-1.0, 0.0, 0.5, 1.0, 4.5, 10.0];
+        let p = vec![0.0, 0.0, 14.0, 6.0, -2.2222222222222222e-01, -1.2];
+        assert::close(
+            &x.iter().map(|&x| d.gradient(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        use std::f64::INFINITY;
+
+        let d = new!(9.0, 0.5);
+        let p = vec![0.0, 0.1, 0.25, 0.5, 0.75, 0.9, 1.0];
+        let x = vec![
+            0.0000000000000000e+00,
+            2.7162340291272155e+00,
+            3.4188225875995731e+00,
+            4.3344755921851861e+00,
+            5.4012224489320413e+00,
+            6.4973557706593031e+00,
+            INFINITY,
+        ];
+
+        assert::close(
+            &p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
+            &x,
+            1e-13,
+        );
+
+        let p = vec![
+            1e-300,
+            1e-100,
+            1e-10,
+            0.1,
+            0.5,
+            0.9,
+            1.0 - 1e-10,
+            1.0 - ::std::f64::EPSILON,
+        ];
+        let cases = vec![
+            (
+                0.1,
+                vec![
+                    0.0,
+                    0.0,
+                    6.0730483624079624e-101,
+                    6.0730483627431838e-11,
+                    5.9339110446022617e-04,
+                    2.6615455373883786e-01,
+                    1.8119637703108019e+01,
+                    3.0681584513882381e+01,
+                ],
+            ),
+            (
+                1.0,
+                vec![
+                    1.0000000000000000e-300,
+                    1.0000000000000000e-100,
+                    1.0000000000500000e-10,
+                    1.0536051565782631e-01,
+                    2f64.ln(),
+                    10f64.ln(),
+                    2.3025850847200089e+01,
+                    3.6043653389117156e+01,
+                ],
+            ),
+            (
+                5.0,
+                vec![
+                    2.6051710846973519e-60,
+                    2.6051710846973519e-20,
+                    2.6165532815952705e-02,
+                    2.4325910259626645e+00,
+                    4.6709088827959837e+00,
+                    7.9935895860526308e+00,
+                    3.4083808975952068e+01,
+                    4.8473895977885152e+01,
+                ],
+            ),
+            (
+                100.0,
+                vec![
+                    3.8006988916941887e-02,
+                    3.9507988085400644e+00,
+                    4.8883092053795954e+01,
+                    8.7417636499593657e+01,
+                    9.9666864919315489e+01,
+                    1.1301052385984448e+02,
+                    1.7730050452110316e+02,
+                    2.0411333547779526e+02,
+                ],
+            ),
+        ];
+        for (k, x) in cases {
+            let d = new!(k, 1.0);
+            for (&p, &x) in p.iter().zip(x.iter()) {
+                if x == 0.0 {
+                    assert_eq!(d.inverse(p), 0.0);
+                } else {
+                    assert::close(d.inverse(p) / x, 1.0, 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_survival() {
+        let d = new!(9.0, 0.5);
+        for &q in &[0.001, 0.1, 0.5, 0.9, 0.999] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-12);
+        }
+        assert::close(
+            d.inverse_survival(1e-100) / 1.3215061883292574e+02,
+            1.0,
+            1e-13,
+        );
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(3.0, 1.5).kurtosis(), 2.0);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(9.0, 0.5);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 4.5, 9.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+        assert::close(d.ln_density(500.0) / -9.5464941349032821e+02, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(9.0, 0.5);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 4.5, 9.0] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-13);
+        }
+        assert::close(
+            d.ln_distribution(1e-3) / -6.8735100349516104e+01,
+            1.0,
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(9.0, 0.5);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 4.5, 9.0] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-13);
+        }
+        assert::close(d.ln_survival(1.0) / -2.3747552334133027e-04, 1.0, 1e-13);
+        assert::close(d.ln_survival(200.0) / -3.6265273560994455e+02, 1.0, 1e-14);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(9.0, 0.5).mean(), 4.5);
     }
 
+    #[test]
+    fn median() {
+        assert::close(new!(9.0, 0.5).median(), 4.3344755921851861e+00, 1e-14);
+        assert::close(new!(1.0, 2.0).median(), 2.0 * 2f64.ln(), 1e-15);
+    }
+
     #[test]
     fn modes() {
         assert_eq!(new!(5.5, 1.5).modes(), vec![6.75]);
@@ -275,6 +811,26 @@ mod tests {
         assert_eq!(new!(4.0, 1.5).skewness(), 1.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(9.0, 0.5);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 4.5, 9.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-14);
+        }
+        assert::close(d.survival(200.0) / 3.1762748620748422e-158, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Gamma::try_new(9.0, 0.5).is_ok());
+        assert_eq!(
+            Gamma::try_new(9.0, -0.5).unwrap_err(),
+            Error::NotPositive("theta")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(9.0, 0.5).variance(), 2.25);
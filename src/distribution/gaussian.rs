@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// A Gaussian distribution.
@@ -25,6 +25,15 @@ impl Gaussian {
         }
     }
 
+    /// Create a Gaussian distribution with mean `mu` and standard deviation
+    /// `sigma` or fail if the parameters are invalid.
+    #[inline]
+    pub fn try_new(mu: f64, sigma: f64) -> Result<Self, Error> {
+        error::finite(mu, "mu")?;
+        error::positive(sigma, "sigma")?;
+        Ok(Gaussian::new(mu, sigma))
+    }
+
     /// Return the mean.
     #[inline(always)]
     pub fn mu(&self) -> f64 {
@@ -49,6 +58,11 @@ impl distribution::Continuous for Gaussian {
     fn density(&self, x: f64) -> f64 {
         (-(x - self.mu).powi(2) / (2.0 * self.sigma * self.sigma)).exp() / self.norm
     }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        -(x - self.mu).powi(2) / (2.0 * self.sigma * self.sigma) - self.norm.ln()
+    }
 }
 
 impl distribution::Distribution for Gaussian {
@@ -59,6 +73,22 @@ impl distribution::Distribution for Gaussian {
         use std::f64::consts::SQRT_2;
         (1.0 + ((x - self.mu) / (self.sigma * SQRT_2)).error()) / 2.0
     }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        ln_distribution((x - self.mu) / self.sigma)
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        ln_distribution((self.mu - x) / self.sigma)
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        use special::Error;
+        use std::f64::consts::SQRT_2;
+        ((x - self.mu) / (self.sigma * SQRT_2)).compl_error() / 2.0
+    }
 }
 
 impl distribution::Entropy for Gaussian {
@@ -69,6 +99,35 @@ impl distribution::Entropy for Gaussian {
     }
 }
 
+impl distribution::Fit for Gaussian {
+    fn fit(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        fit::check(data, 2)?;
+        let mu = fit::mean(data.iter().cloned());
+        let sigma = fit::variance(data.iter().cloned(), mu).sqrt();
+        Ok(fit::continuous(
+            Gaussian::try_new(mu, sigma)?,
+            data,
+            0,
+            true,
+        ))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    ///
+    /// The estimate coincides with the maximum-likelihood one.
+    #[inline]
+    fn fit_moments(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        Self::fit(data)
+    }
+}
+
+impl distribution::Gradient for Gaussian {
+    #[inline]
+    fn gradient(&self, x: f64) -> f64 {
+        (self.mu - x) / (self.sigma * self.sigma)
+    }
+}
+
 impl distribution::Inverse for Gaussian {
     /// Compute the inverse of the cumulative distribution function.
     ///
@@ -83,6 +142,11 @@ impl distribution::Inverse for Gaussian {
     fn inverse(&self, p: f64) -> f64 {
         self.mu + self.sigma * inverse(p)
     }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        self.mu - self.sigma * inverse(q)
+    }
 }
 
 impl distribution::Kurtosis for Gaussian {
@@ -292,6 +356,24 @@ pub fn sample<S: Source>(source: &mut S) -> f64 {
     }
 }
 
+// Compute the logarithm of the distribution function of the standard Gaussian
+// distribution. Deep in the lower tail, where the complementary error function
+// underflows, the asymptotic expansion of Mills’ ratio is used instead.
+fn ln_distribution(z: f64) -> f64 {
+    use special::Error;
+    use std::f64::consts::{PI, SQRT_2};
+
+    if z > 0.0 {
+        (-0.5 * (z / SQRT_2).compl_error()).ln_1p()
+    } else if z > -37.0 {
+        (0.5 * (-z / SQRT_2).compl_error()).ln()
+    } else {
+        let t = 1.0 / (z * z);
+        let series = t * (-1.0 + t * (3.0 + t * (-15.0 + t * (105.0 - t * 945.0))));
+        -0.5 * z * z - (-z).ln() - 0.5 * (2.0 * PI).ln() + series.ln_1p()
+    }
+}
+
 const R: f64 = 3.44428647676;
 
 const K: [u32; 128] = [
@@ -482,6 +564,44 @@ mod tests {
         assert_eq!(new!(0.0, 1.0).entropy(), ((2.0 * PI).ln() + 1.0) / 2.0);
     }
 
+    #[test]
+    fn fit() {
+        use distribution::{Error, FitError};
+
+        let data = vec![-1.3, -0.4, 0.2, 0.5, 1.1, 1.7, 2.6, 4.0];
+        let estimate = Gaussian::fit(&data).unwrap();
+        assert::close(estimate.distribution.mu(), 1.05, 1e-15);
+        assert::close(estimate.distribution.sigma(), 1.5882380174268591, 1e-15);
+        assert::close(estimate.ln_likelihood, -1.5052510158619890e+01, 1e-13);
+        assert!(estimate.converged);
+
+        assert_eq!(Gaussian::fit(&[1.0]).unwrap_err(), FitError::Insufficient);
+        assert_eq!(
+            Gaussian::fit(&[1.0, 1.0]).unwrap_err(),
+            FitError::Parameter(Error::NotPositive("sigma"))
+        );
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![-1.3, -0.4, 0.2, 0.5, 1.1, 1.7, 2.6, 4.0];
+        let estimate = Gaussian::fit_moments(&data).unwrap();
+        assert::close(estimate.distribution.mu(), 1.05, 1e-15);
+        assert::close(estimate.distribution.sigma(), 1.5882380174268591, 1e-15);
+    }
+
+    #[test]
+    fn gradient() {
+        let d = new!(1.0, 2.0);
+        let x = vec![-1.0, 0.0, 1.0, 2.5];
+        let p = vec![0.5, 0.25, 0.0, -0.375];
+        assert::close(
+            &x.iter().map(|&x| d.gradient(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
     #[test]
     fn inverse() {
         use std::f64::{INFINITY, NEG_INFINITY};
@@ -522,11 +642,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(1.0, 2.0);
+        for &q in &[0.001, 0.1, 0.5, 0.9, 0.999] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-12);
+        }
+        let d = new!(0.0, 1.0);
+        assert::close(d.inverse_survival(1e-20), 9.2623400897984076, 1e-14);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(0.0, 2.0).kurtosis(), 0.0);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+        assert::close(d.ln_density(1e3), -1.2475173708571376e+05, 1e-9);
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+        let d = new!(0.0, 1.0);
+        assert::close(
+            d.ln_distribution(-30.0) / -4.5432124395634320e+02,
+            1.0,
+            1e-14,
+        );
+        assert::close(
+            d.ln_distribution(-40.0) / -8.0460844201375379e+02,
+            1.0,
+            1e-14,
+        );
+        assert::close(
+            d.ln_distribution(-100.0) / -5.0055242086942051e+03,
+            1.0,
+            1e-14,
+        );
+        assert::close(
+            d.ln_distribution(10.0) / -7.6198530241605261e-24,
+            1.0,
+            1e-14,
+        );
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-14);
+        }
+        let d = new!(0.0, 1.0);
+        assert::close(d.ln_survival(40.0) / -8.0460844201375379e+02, 1.0, 1e-14);
+        assert::close(d.ln_survival(-10.0) / -7.6198530241605261e-24, 1.0, 1e-14);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(0.0, 1.0).mean(), 0.0);
@@ -547,6 +726,31 @@ mod tests {
         assert_eq!(new!(0.0, 2.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-15);
+        }
+        let d = new!(0.0, 1.0);
+        assert::close(d.survival(10.0) / 7.6198530241605261e-24, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Gaussian::try_new(1.0, 2.0).is_ok());
+        assert_eq!(
+            Gaussian::try_new(::std::f64::INFINITY, 2.0).unwrap_err(),
+            Error::NotFinite("mu")
+        );
+        assert_eq!(
+            Gaussian::try_new(1.0, -2.0).unwrap_err(),
+            Error::NotPositive("sigma")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(0.0, 2.0).variance(), 4.0);
@@ -1,5 +1,5 @@
-use distribution;
 use distribution::Inverse;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// A Laplace distribution.
@@ -19,6 +19,15 @@ impl Laplace {
         Laplace { mu: mu, b: b }
     }
 
+    /// Create a Laplace distribution with location `mu` and scale `b` or fail
+    /// if the parameters are invalid.
+    #[inline]
+    pub fn try_new(mu: f64, b: f64) -> Result<Self, Error> {
+        error::finite(mu, "mu")?;
+        error::positive(b, "b")?;
+        Ok(Laplace::new(mu, b))
+    }
+
     // Return the location parameter
     #[inline(always)]
     pub fn mu(&self) -> f64 {
@@ -37,6 +46,11 @@ impl distribution::Continuous for Laplace {
     fn density(&self, x: f64) -> f64 {
         self.b.recip() * 0.5 * (-(x - self.mu).abs() / self.b).exp()
     }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        -(2.0 * self.b).ln() - (x - self.mu).abs() / self.b
+    }
 }
 
 impl distribution::Distribution for Laplace {
@@ -50,6 +64,25 @@ impl distribution::Distribution for Laplace {
             1.0 - 0.5 * (-(x - self.mu) / self.b).exp()
         }
     }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        ln_distribution((x - self.mu) / self.b)
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        ln_distribution((self.mu - x) / self.b)
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        if x >= self.mu {
+            0.5 * (-(x - self.mu) / self.b).exp()
+        } else {
+            1.0 - 0.5 * ((x - self.mu) / self.b).exp()
+        }
+    }
 }
 
 impl distribution::Entropy for Laplace {
@@ -59,6 +92,34 @@ impl distribution::Entropy for Laplace {
     }
 }
 
+impl distribution::Fit for Laplace {
+    fn fit(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        fit::check(data, 2)?;
+        let mu = fit::median(data);
+        let b = fit::mean(data.iter().map(|&x| (x - mu).abs()));
+        Ok(fit::continuous(Laplace::try_new(mu, b)?, data, 0, true))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    fn fit_moments(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        fit::check(data, 2)?;
+        let mu = fit::mean(data.iter().cloned());
+        let b = (0.5 * fit::variance(data.iter().cloned(), mu)).sqrt();
+        Ok(fit::continuous(Laplace::try_new(mu, b)?, data, 0, true))
+    }
+}
+
+impl distribution::Gradient for Laplace {
+    #[inline]
+    fn gradient(&self, x: f64) -> f64 {
+        if x == self.mu {
+            0.0
+        } else {
+            -(x - self.mu).signum() / self.b
+        }
+    }
+}
+
 impl distribution::Inverse for Laplace {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
@@ -75,6 +136,22 @@ impl distribution::Inverse for Laplace {
             self.mu + self.b * (2.0 * p).ln()
         }
     }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        should!(0.0 <= q && q <= 1.0);
+        if q > 0.5 {
+            if q == 1.0 {
+                return std::f64::NEG_INFINITY;
+            }
+            self.mu + self.b * (2.0 - 2.0 * q).ln()
+        } else {
+            if q == 0.0 {
+                return std::f64::INFINITY;
+            }
+            self.mu - self.b * (2.0 * q).ln()
+        }
+    }
 }
 
 impl distribution::Kurtosis for Laplace {
@@ -134,6 +211,17 @@ impl distribution::Variance for Laplace {
     }
 }
 
+// Compute the logarithm of the distribution function of the standard Laplace
+// distribution.
+fn ln_distribution(z: f64) -> f64 {
+    use std::f64::consts::LN_2;
+    if z <= 0.0 {
+        z - LN_2
+    } else {
+        (-0.5 * (-z).exp()).ln_1p()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert;
@@ -203,6 +291,36 @@ mod tests {
         assert_eq!(new!(2.0, 1.0).entropy(), (2.0 * 1.0 * E).ln());
     }
 
+    #[test]
+    fn fit() {
+        let data = vec![-1.3, -0.4, 0.2, 0.5, 1.1, 1.7, 2.6, 4.0];
+        let estimate = Laplace::fit(&data).unwrap();
+        assert::close(estimate.distribution.mu(), 0.8, 1e-15);
+        assert::close(estimate.distribution.b(), 1.3, 1e-15);
+        assert::close(estimate.ln_likelihood, -1.5644091560219492e+01, 1e-13);
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![-1.3, -0.4, 0.2, 0.5, 1.1, 1.7, 2.6, 4.0];
+        let estimate = Laplace::fit_moments(&data).unwrap();
+        assert::close(estimate.distribution.mu(), 1.05, 1e-15);
+        assert::close(estimate.distribution.b(), 1.1230538722608101, 1e-15);
+        assert::close(estimate.ln_likelihood, -1.5734054656064648e+01, 1e-13);
+    }
+
+    #[test]
+    fn gradient() {
+        let d = new!(1.0, 2.0);
+        let x = vec![-3.0, 0.0, 1.0, 1.5, 5.0];
+        let p = vec![0.5, 0.5, 0.0, -0.5, -0.5];
+        assert::close(
+            &x.iter().map(|&x| d.gradient(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
     #[test]
     fn inverse() {
         let d = new!(2.0, 3.0);
@@ -224,11 +342,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(1.0, 2.0);
+        for &q in &[0.0, 0.001, 0.1, 0.5, 0.9, 0.999, 1.0] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-12);
+        }
+        assert::close(
+            d.inverse_survival(1e-300),
+            1.0 - 2.0 * 2e-300f64.ln(),
+            1e-12,
+        );
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(2.0, 9.0).kurtosis(), 3.0);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+        assert::close(
+            new!(0.0, 1.0).ln_distribution(-1e3),
+            -1e3 - 2f64.ln(),
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-14);
+        }
+        assert::close(new!(0.0, 1.0).ln_survival(1e3), -1e3 - 2f64.ln(), 1e-12);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(2.0, 1.0).mean(), 2.0);
@@ -249,6 +410,26 @@ mod tests {
         assert_eq!(new!(2.0, 1.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-15);
+        }
+        assert::close(d.survival(1e3) / (0.5 * (-499.5f64).exp()), 1.0, 1e-13);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Laplace::try_new(1.0, 2.0).is_ok());
+        assert_eq!(
+            Laplace::try_new(1.0, 0.0).unwrap_err(),
+            Error::NotPositive("b")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(2.0, 3.0).variance(), 18.0);
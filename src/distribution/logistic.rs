@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// A logistic distribution.
@@ -18,6 +18,15 @@ impl Logistic {
         Logistic { mu: mu, s: s }
     }
 
+    /// Create a logistic distribution with location `mu` and scale `s` or fail
+    /// if the parameters are invalid.
+    #[inline]
+    pub fn try_new(mu: f64, s: f64) -> Result<Self, Error> {
+        error::finite(mu, "mu")?;
+        error::positive(s, "s")?;
+        Ok(Logistic::new(mu, s))
+    }
+
     /// Return the location parameter.
     #[inline(always)]
     pub fn mu(&self) -> f64 {
@@ -44,6 +53,12 @@ impl distribution::Continuous for Logistic {
         let exp = (-(x - self.mu) / self.s).exp();
         exp / (self.s * (1.0 + exp).powi(2))
     }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        let z = -((x - self.mu) / self.s).abs();
+        z - self.s.ln() - 2.0 * z.exp().ln_1p()
+    }
 }
 
 impl distribution::Distribution for Logistic {
@@ -53,6 +68,21 @@ impl distribution::Distribution for Logistic {
     fn distribution(&self, x: f64) -> f64 {
         1.0 / (1.0 + (-(x - self.mu) / self.s).exp())
     }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        ln_distribution((x - self.mu) / self.s)
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        ln_distribution((self.mu - x) / self.s)
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        1.0 / (1.0 + ((x - self.mu) / self.s).exp())
+    }
 }
 
 impl distribution::Entropy for Logistic {
@@ -62,12 +92,85 @@ impl distribution::Entropy for Logistic {
     }
 }
 
+impl distribution::Fit for Logistic {
+    /// Estimate the parameters by the method of maximum likelihood.
+    ///
+    /// The likelihood equations are solved by the Newton method starting
+    /// from the method-of-moments estimate.
+    fn fit(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        let start = Self::fit_moments(data)?.distribution;
+        let (mut mu, mut s) = (start.mu, start.s);
+        let n = data.len() as f64;
+        let mut converged = false;
+        let mut iterations = 0;
+        while iterations < fit::ITERATIONS {
+            iterations += 1;
+            let (mut g1, mut g2) = (0.0, -n);
+            let (mut j11, mut j12, mut j21, mut j22) = (0.0, 0.0, 0.0, 0.0);
+            for &x in data {
+                let z = (x - mu) / s;
+                let t = (0.5 * z).tanh();
+                let dt = 0.5 * (1.0 - t * t);
+                g1 += t;
+                g2 += z * t;
+                j11 -= dt / s;
+                j12 -= dt * z / s;
+                j21 -= (t + z * dt) / s;
+                j22 -= (t + z * dt) * z / s;
+            }
+            let determinant = j11 * j22 - j12 * j21;
+            let delta_mu = (g1 * j22 - g2 * j12) / determinant;
+            let mut delta_s = (g2 * j11 - g1 * j21) / determinant;
+            if !delta_mu.is_finite() || !delta_s.is_finite() {
+                break;
+            }
+            if s - delta_s <= 0.0 {
+                delta_s = 0.5 * s;
+            }
+            mu -= delta_mu;
+            s -= delta_s;
+            if delta_mu.abs() < fit::TOLERANCE * s && delta_s.abs() < fit::TOLERANCE * s {
+                converged = true;
+                break;
+            }
+        }
+        Ok(fit::continuous(
+            Logistic::try_new(mu, s)?,
+            data,
+            iterations,
+            converged,
+        ))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    fn fit_moments(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        use std::f64::consts::PI;
+        fit::check(data, 2)?;
+        let mu = fit::mean(data.iter().cloned());
+        let s = (3.0 * fit::variance(data.iter().cloned(), mu)).sqrt() / PI;
+        Ok(fit::continuous(Logistic::try_new(mu, s)?, data, 0, true))
+    }
+}
+
+impl distribution::Gradient for Logistic {
+    #[inline]
+    fn gradient(&self, x: f64) -> f64 {
+        -((x - self.mu) / (2.0 * self.s)).tanh() / self.s
+    }
+}
+
 impl distribution::Inverse for Logistic {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
         should!(0.0 <= p && p <= 1.0);
         self.mu - self.s * (1.0 / p - 1.0).ln()
     }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        should!(0.0 <= q && q <= 1.0);
+        self.mu + self.s * (1.0 / q - 1.0).ln()
+    }
 }
 
 impl distribution::Kurtosis for Logistic {
@@ -124,6 +227,16 @@ impl distribution::Variance for Logistic {
     }
 }
 
+// Compute the logarithm of the distribution function of the standard logistic
+// distribution.
+fn ln_distribution(z: f64) -> f64 {
+    if z >= 0.0 {
+        -(-z).exp().ln_1p()
+    } else {
+        z - z.exp().ln_1p()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use assert;
@@ -189,6 +302,43 @@ mod tests {
         assert_eq!(new!(0.0, (-2f64).exp()).entropy(), 0.0);
     }
 
+    #[test]
+    fn fit() {
+        let data = vec![-1.3, -0.4, 0.2, 0.5, 1.1, 1.7, 2.6, 4.0];
+        let estimate = Logistic::fit(&data).unwrap();
+        assert::close(estimate.distribution.mu(), 9.5816323169917550e-01, 1e-12);
+        assert::close(estimate.distribution.s(), 9.2789927407543293e-01, 1e-12);
+        assert::close(estimate.ln_likelihood, -1.5248987590324647e+01, 1e-12);
+        assert!(estimate.converged);
+        assert!(estimate.iterations > 0);
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![-1.3, -0.4, 0.2, 0.5, 1.1, 1.7, 2.6, 4.0];
+        let estimate = Logistic::fit_moments(&data).unwrap();
+        assert::close(estimate.distribution.mu(), 1.05, 1e-15);
+        assert::close(estimate.distribution.s(), 8.7564151181484717e-01, 1e-15);
+        assert::close(estimate.ln_likelihood, -1.5284816136513351e+01, 1e-13);
+    }
+
+    #[test]
+    fn gradient() {
+        let d = new!(1.0, 2.0);
+        let x = vec![-3.0, 0.0, 1.5, 5.0];
+        let p = vec![
+            3.8079707797788244e-01,
+            1.2245933120185456e-01,
+            -6.2176500885798104e-02,
+            -3.8079707797788244e-01,
+        ];
+        assert::close(
+            &x.iter().map(|&x| d.gradient(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
     #[test]
     fn inverse() {
         let d = new!(5.0, 5.0);
@@ -214,11 +364,48 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(1.0, 2.0);
+        for &q in &[0.001, 0.1, 0.5, 0.9, 0.999] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-12);
+        }
+        assert::close(d.inverse_survival(1e-300), 1.0 + 600.0 * 10f64.ln(), 1e-12);
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(2.0, 1.0).kurtosis(), 1.2);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+        assert::close(d.ln_density(-2e3), -1.0011931471805599e+03, 1e-12);
+        assert::close(d.ln_density(2e3 + 2.0), -1.0011931471805599e+03, 1e-12);
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+        assert_eq!(d.ln_distribution(-2e3), -1000.5);
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-14);
+        }
+        assert_eq!(d.ln_survival(2e3 + 2.0), -1000.5);
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(2.0, 1.0).mean(), 2.0);
@@ -239,6 +426,26 @@ mod tests {
         assert_eq!(new!(2.0, 1.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-15);
+        }
+        assert::close(d.survival(1e3) / (-499.5f64).exp(), 1.0, 1e-13);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Logistic::try_new(1.0, 2.0).is_ok());
+        assert_eq!(
+            Logistic::try_new(1.0, 0.0).unwrap_err(),
+            Error::NotPositive("s")
+        );
+    }
+
     #[test]
     fn variance() {
         use std::f64::consts::PI;
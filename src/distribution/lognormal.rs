@@ -1,4 +1,4 @@
-use distribution::{self, Gaussian};
+use distribution::{self, error, fit, Error, Estimate, FitError, Gaussian};
 use source::Source;
 
 /// A lognormal distribution.
@@ -23,6 +23,15 @@ impl Lognormal {
         }
     }
 
+    /// Create a lognormal distribution with location `mu` and scale `sigma` or
+    /// fail if the parameters are invalid.
+    #[inline]
+    pub fn try_new(mu: f64, sigma: f64) -> Result<Self, Error> {
+        error::finite(mu, "mu")?;
+        error::positive(sigma, "sigma")?;
+        Ok(Lognormal::new(mu, sigma))
+    }
+
     /// Return the location parameter.
     #[inline(always)]
     pub fn mu(&self) -> f64 {
@@ -53,6 +62,15 @@ impl distribution::Continuous for Lognormal {
             (-(x.ln() - mu).powi(2) / (2.0 * sigma * sigma)).exp() / (x * sigma * (2.0 * PI).sqrt())
         }
     }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            ::std::f64::NEG_INFINITY
+        } else {
+            let x = x.ln();
+            self.gaussian.ln_density(x) - x
+        }
+    }
 }
 
 impl distribution::Distribution for Lognormal {
@@ -65,6 +83,30 @@ impl distribution::Distribution for Lognormal {
             self.gaussian.distribution(x.ln())
         }
     }
+
+    fn ln_distribution(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            ::std::f64::NEG_INFINITY
+        } else {
+            self.gaussian.ln_distribution(x.ln())
+        }
+    }
+
+    fn ln_survival(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            self.gaussian.ln_survival(x.ln())
+        }
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            1.0
+        } else {
+            self.gaussian.survival(x.ln())
+        }
+    }
 }
 
 impl distribution::Entropy for Lognormal {
@@ -75,10 +117,60 @@ impl distribution::Entropy for Lognormal {
     }
 }
 
+impl distribution::Fit for Lognormal {
+    fn fit(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        fit::check(data, 2)?;
+        if data.iter().any(|&x| x <= 0.0) {
+            return Err(FitError::OutOfSupport);
+        }
+        let mu = fit::mean(data.iter().map(|x| x.ln()));
+        let sigma = fit::variance(data.iter().map(|x| x.ln()), mu).sqrt();
+        Ok(fit::continuous(
+            Lognormal::try_new(mu, sigma)?,
+            data,
+            0,
+            true,
+        ))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    fn fit_moments(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        fit::check(data, 2)?;
+        if data.iter().any(|&x| x <= 0.0) {
+            return Err(FitError::OutOfSupport);
+        }
+        let mean = fit::mean(data.iter().cloned());
+        let variance = fit::variance(data.iter().cloned(), mean);
+        let sigma2 = (variance / (mean * mean)).ln_1p();
+        let mu = mean.ln() - 0.5 * sigma2;
+        Ok(fit::continuous(
+            Lognormal::try_new(mu, sigma2.sqrt())?,
+            data,
+            0,
+            true,
+        ))
+    }
+}
+
+impl distribution::Gradient for Lognormal {
+    #[inline]
+    fn gradient(&self, x: f64) -> f64 {
+        if x <= 0.0 {
+            0.0
+        } else {
+            -(1.0 + (x.ln() - self.mu) / (self.sigma * self.sigma)) / x
+        }
+    }
+}
+
 impl distribution::Inverse for Lognormal {
     fn inverse(&self, p: f64) -> f64 {
         self.gaussian.inverse(p).exp()
     }
+
+    fn inverse_survival(&self, q: f64) -> f64 {
+        self.gaussian.inverse_survival(q).exp()
+    }
 }
 
 impl distribution::Kurtosis for Lognormal {
@@ -201,6 +293,50 @@ mod tests {
         assert_eq!(new!(-0.5, 1.0 / (2.0 * PI).sqrt()).entropy(), 0.0);
     }
 
+    #[test]
+    fn fit() {
+        use distribution::FitError;
+
+        let data = vec![0.5, 1.2, 2.0, 2.7, 3.1, 4.4, 5.9, 8.3];
+        let estimate = Lognormal::fit(&data).unwrap();
+        assert::close(estimate.distribution.mu(), 9.5997348099172253e-01, 1e-15);
+        assert::close(estimate.distribution.sigma(), 8.4502363830473659e-01, 1e-15);
+        assert::close(estimate.ln_likelihood, -1.7684170692042319e+01, 1e-13);
+
+        assert_eq!(
+            Lognormal::fit(&[1.0, 0.0]).unwrap_err(),
+            FitError::OutOfSupport
+        );
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![0.5, 1.2, 2.0, 2.7, 3.1, 4.4, 5.9, 8.3];
+        let estimate = Lognormal::fit_moments(&data).unwrap();
+        assert::close(estimate.distribution.mu(), 1.0617735984569245e+00, 1e-15);
+        assert::close(estimate.distribution.sigma(), 6.2378591873003941e-01, 1e-15);
+        assert::close(estimate.ln_likelihood, -1.8702760761285109e+01, 1e-13);
+    }
+
+    #[test]
+    fn gradient() {
+        let d = new!(1.0, 2.0);
+        let x = vec![-1.0, 0.0, 0.1, 1.0, 2.5, 10.0];
+        let p = vec![
+            0.0,
+            0.0,
+            -1.7435372675148858e+00,
+            -0.75,
+            -3.9162907318741551e-01,
+            -1.3256462732485114e-01,
+        ];
+        assert::close(
+            &x.iter().map(|&x| d.gradient(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
     #[test]
     fn inverse() {
         use std::f64::INFINITY;
@@ -240,11 +376,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(1.0, 2.0);
+        for &q in &[0.001, 0.1, 0.5, 0.9, 0.999] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-10);
+        }
+        let d = new!(0.0, 1.0);
+        assert::close(d.inverse_survival(1e-20).ln(), 9.2623400897984076, 1e-14);
+    }
+
     #[test]
     fn kurtosis() {
         assert::close(new!(0.0, 1.0).kurtosis(), 1.1093639217631153e+02, 1e-15);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+        let d = new!(0.0, 1.0);
+        assert::close(d.ln_density(1e-100) / -2.6280150981625790e+04, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 2.5, 10.0] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-14);
+        }
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(-2.0, 2.0).mean(), 1.0);
@@ -265,6 +437,27 @@ mod tests {
         assert!(4.0 - new!(0.0, 2f64.ln().sqrt()).skewness() < 1e-10);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(1.0, 2.0);
+        for &x in &[-1.0, 0.0, 0.5, 1.0, 2.5, 10.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-15);
+        }
+        let d = new!(0.0, 1.0);
+        assert::close(d.survival(10f64.exp()) / 7.6198530241605261e-24, 1.0, 1e-13);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Lognormal::try_new(1.0, 2.0).is_ok());
+        assert_eq!(
+            Lognormal::try_new(1.0, -2.0).unwrap_err(),
+            Error::NotPositive("sigma")
+        );
+    }
+
     #[test]
     fn variance() {
         assert!(2.0 - new!(0.0, 2f64.ln().sqrt()).variance() < 1e-10);
@@ -6,12 +6,24 @@ use source::Source;
 pub trait Continuous: Distribution {
     /// Compute the probability density function.
     fn density(&self, f64) -> f64;
+
+    /// Compute the natural logarithm of the probability density function.
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        self.density(x).ln()
+    }
 }
 
 /// A discrete distribution.
 pub trait Discrete: Distribution {
     /// Compute the probability mass function.
     fn mass(&self, Self::Value) -> f64;
+
+    /// Compute the natural logarithm of the probability mass function.
+    #[inline]
+    fn ln_mass(&self, x: Self::Value) -> f64 {
+        self.mass(x).ln()
+    }
 }
 
 /// A distribution.
@@ -21,6 +33,25 @@ pub trait Distribution {
 
     /// Compute the cumulative distribution function.
     fn distribution(&self, f64) -> f64;
+
+    /// Compute the natural logarithm of the cumulative distribution function.
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        self.distribution(x).ln()
+    }
+
+    /// Compute the natural logarithm of the survival function.
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        self.survival(x).ln()
+    }
+
+    /// Compute the survival function, that is, the complement of the
+    /// cumulative distribution function.
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        1.0 - self.distribution(x)
+    }
 }
 
 /// A distribution capable of computing the differential entropy.
@@ -31,10 +62,35 @@ pub trait Entropy: Distribution {
     fn entropy(&self) -> f64;
 }
 
+/// A distribution capable of estimating its parameters from data.
+pub trait Fit: Distribution + Sized {
+    /// Estimate the parameters by the method of maximum likelihood.
+    fn fit(&[Self::Value]) -> Result<Estimate<Self>, FitError>;
+
+    /// Estimate the parameters by the method of moments.
+    fn fit_moments(&[Self::Value]) -> Result<Estimate<Self>, FitError>;
+}
+
+/// A continuous distribution capable of computing the derivative of the
+/// logarithm of the probability density function.
+pub trait Gradient: Continuous {
+    /// Compute the derivative of the natural logarithm of the probability
+    /// density function with respect to the value.
+    ///
+    /// The derivative is zero outside the interior of the support.
+    fn gradient(&self, f64) -> f64;
+}
+
 /// A distribution capable of inverting the distribution function.
 pub trait Inverse: Distribution {
     /// Compute the inverse of the cumulative distribution function.
     fn inverse(&self, f64) -> Self::Value;
+
+    /// Compute the inverse of the survival function.
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> Self::Value {
+        self.inverse(1.0 - q)
+    }
 }
 
 /// A distribution capable of computing the excess kurtosis.
@@ -99,26 +155,58 @@ mod bernoulli;
 mod beta;
 mod binomial;
 mod categorical;
+mod chi_squared;
+mod dirichlet;
+mod dynamic;
+mod error;
 mod exponential;
+mod fisher_snedecor;
+mod fit;
 mod gamma;
 mod gaussian;
 mod laplace;
 mod logistic;
 mod lognormal;
+mod multinomial;
+mod multivariate_gaussian;
+mod notation;
 mod pert;
+mod poisson;
+#[cfg(feature = "rand")]
+mod rand;
+#[cfg(feature = "serde")]
+mod serialization;
+mod students_t;
 mod triangular;
 mod uniform;
+mod weighted_categorical;
 
 pub use self::bernoulli::Bernoulli;
 pub use self::beta::Beta;
 pub use self::binomial::Binomial;
 pub use self::categorical::Categorical;
+pub use self::chi_squared::ChiSquared;
+pub use self::dirichlet::Dirichlet;
+pub use self::dynamic::AnyDistribution;
+pub use self::dynamic::DynContinuous;
+pub use self::dynamic::DynDiscrete;
+pub use self::error::Error;
 pub use self::exponential::Exponential;
+pub use self::fisher_snedecor::FisherSnedecor;
+pub use self::fit::Estimate;
+pub use self::fit::FitError;
 pub use self::gamma::Gamma;
 pub use self::gaussian::Gaussian;
 pub use self::laplace::Laplace;
 pub use self::logistic::Logistic;
 pub use self::lognormal::Lognormal;
+pub use self::multinomial::Multinomial;
+pub use self::multivariate_gaussian::MultivariateGaussian;
+pub use self::notation::ParseError;
+pub use self::notation::ParseErrorKind;
 pub use self::pert::Pert;
+pub use self::poisson::Poisson;
+pub use self::students_t::StudentsT;
 pub use self::triangular::Triangular;
 pub use self::uniform::Uniform;
+pub use self::weighted_categorical::WeightedCategorical;
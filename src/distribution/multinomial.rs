@@ -0,0 +1,210 @@
+use distribution::{error, Error};
+use source::Source;
+
+/// A multinomial distribution.
+#[derive(Clone, Debug)]
+pub struct Multinomial {
+    n: usize,
+    k: usize,
+    p: Vec<f64>,
+    tail: Vec<f64>,
+}
+
+impl Multinomial {
+    /// Create a multinomial distribution with `n` trials and success
+    /// probability `p`.
+    ///
+    /// It should hold that `p[i] >= 0`, `p[i] <= 1`, and `sum(p) == 1`.
+    pub fn new(n: usize, p: &[f64]) -> Self {
+        should!(error::probability_vector(p, "p").is_ok());
+        let k = p.len();
+        let mut tail = p.to_vec();
+        for i in (0..(k - 1)).rev() {
+            tail[i] += tail[i + 1];
+        }
+        Multinomial {
+            n: n,
+            k: k,
+            p: p.to_vec(),
+            tail: tail,
+        }
+    }
+
+    /// Create a multinomial distribution with `n` trials and success
+    /// probability `p` or fail if the parameters are invalid.
+    pub fn try_new(n: usize, p: &[f64]) -> Result<Self, Error> {
+        error::probability_vector(p, "p")?;
+        Ok(Multinomial::new(n, p))
+    }
+
+    /// Return the number of trials.
+    #[inline(always)]
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Return the number of categories.
+    #[inline(always)]
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// Return the success probabilities.
+    #[inline(always)]
+    pub fn p(&self) -> &[f64] {
+        &self.p
+    }
+
+    /// Compute the covariance matrix in row-major order.
+    pub fn covariance(&self) -> Vec<f64> {
+        let (n, k) = (self.n as f64, self.k);
+        let mut covariance = vec![0.0; k * k];
+        for i in 0..k {
+            for j in 0..k {
+                covariance[i * k + j] = if i == j {
+                    n * self.p[i] * (1.0 - self.p[i])
+                } else {
+                    -n * self.p[i] * self.p[j]
+                };
+            }
+        }
+        covariance
+    }
+
+    /// Compute the natural logarithm of the probability mass function.
+    pub fn ln_mass(&self, x: &[usize]) -> f64 {
+        use special::Gamma;
+
+        should!(x.len() == self.k);
+        if x.iter().fold(0, |sum, &x| sum + x) != self.n {
+            return ::std::f64::NEG_INFINITY;
+        }
+        let mut sum = (self.n as f64 + 1.0).ln_gamma().0;
+        for (&p, &x) in self.p.iter().zip(x) {
+            if x > 0 {
+                let x = x as f64;
+                sum += x * p.ln() - (x + 1.0).ln_gamma().0;
+            }
+        }
+        sum
+    }
+
+    /// Compute the probability mass function.
+    #[inline]
+    pub fn mass(&self, x: &[usize]) -> f64 {
+        self.ln_mass(x).exp()
+    }
+
+    /// Compute the mean vector.
+    pub fn mean(&self) -> Vec<f64> {
+        let n = self.n as f64;
+        self.p.iter().map(|&p| n * p).collect()
+    }
+
+    /// Draw a sample and write it into `x`.
+    ///
+    /// The counts are drawn one category at a time from binomial
+    /// distributions conditioned on the counts drawn so far. The last category
+    /// with a positive probability receives the remaining trials.
+    pub fn sample<S>(&self, source: &mut S, x: &mut [usize])
+    where
+        S: Source,
+    {
+        use distribution::{Binomial, Sample};
+
+        should!(x.len() == self.k);
+        let mut n = self.n;
+        for i in 0..self.k {
+            let p = self.p[i] / self.tail[i];
+            x[i] = if n == 0 || p <= 0.0 {
+                0
+            } else if p >= 1.0 {
+                n
+            } else {
+                Binomial::new(n, p).sample(source)
+            };
+            n -= x[i];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($n:expr, $p:expr) => (Multinomial::new($n, &$p));
+    );
+
+    #[test]
+    fn covariance() {
+        let d = new!(10, [0.2, 0.5, 0.3]);
+        let covariance = vec![1.6, -1.0, -0.6, -1.0, 2.5, -1.5, -0.6, -1.5, 2.1];
+        assert::close(&d.covariance(), &covariance, 1e-14);
+    }
+
+    #[test]
+    fn ln_mass() {
+        let d = new!(10, [0.2, 0.5, 0.3]);
+        let x = vec![[2, 5, 3], [0, 10, 0], [10, 0, 0]];
+        let p = vec![
+            -2.4645159601402664e+00,
+            -6.9314718055994531e+00,
+            -1.6094379124341003e+01,
+        ];
+        assert::close(
+            &x.iter().map(|x| d.ln_mass(x)).collect::<Vec<_>>(),
+            &p,
+            1e-13,
+        );
+        assert_eq!(d.ln_mass(&[2, 5, 4]), ::std::f64::NEG_INFINITY);
+
+        let d = new!(16, [0.25, 0.75]);
+        let binomial = Binomial::new(16, 0.25);
+        assert::close(d.ln_mass(&[5, 11]), binomial.ln_mass(5), 1e-13);
+    }
+
+    #[test]
+    fn mass() {
+        let d = new!(10, [0.2, 0.5, 0.3]);
+        assert::close(d.mass(&[0, 10, 0]), 0.5f64.powi(10), 1e-15);
+        assert_eq!(d.mass(&[2, 5, 4]), 0.0);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!(10, [0.2, 0.5, 0.3]).mean(), vec![2.0, 5.0, 3.0]);
+    }
+
+    #[test]
+    fn sample() {
+        let mut source = source::default();
+        let d = new!(20, [0.2, 0.0, 0.5, 0.3]);
+        let n = 10000;
+        let mut x = [0; 4];
+        let mut sum = [0.0; 4];
+        for _ in 0..n {
+            d.sample(&mut source, &mut x);
+            assert_eq!(x.iter().fold(0, |sum, &x| sum + x), 20);
+            assert_eq!(x[1], 0);
+            for i in 0..4 {
+                sum[i] += x[i] as f64;
+            }
+        }
+        for (&sum, &mean) in sum.iter().zip(&d.mean()) {
+            assert!((sum / n as f64 - mean).abs() < 0.1);
+        }
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Multinomial::try_new(10, &[0.2, 0.8]).is_ok());
+        assert_eq!(
+            Multinomial::try_new(10, &[0.2, 0.7]).unwrap_err(),
+            Error::NotNormalized("p")
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use distribution::{error, gaussian, Error};
+use source::Source;
+
+/// A multivariate Gaussian distribution.
+///
+/// The covariance matrix is stored in row-major order and factored once using
+/// the Cholesky decomposition, which is then used for computing the density
+/// and drawing samples.
+#[derive(Clone, Debug)]
+pub struct MultivariateGaussian {
+    d: usize,
+    mu: Vec<f64>,
+    sigma: Vec<f64>,
+    cholesky: Vec<f64>,
+    ln_norm: f64,
+}
+
+impl MultivariateGaussian {
+    /// Create a multivariate Gaussian distribution with mean vector `mu` and
+    /// covariance matrix `sigma`.
+    ///
+    /// The covariance matrix is given in row-major order. It should be
+    /// symmetric and positive definite; only its lower triangle is read.
+    ///
+    /// ## Panics
+    ///
+    /// The function panics if the parameters are invalid, since the
+    /// decomposition cannot be computed otherwise. Use `try_new` in order to
+    /// handle such cases gracefully.
+    pub fn new(mu: &[f64], sigma: &[f64]) -> Self {
+        match MultivariateGaussian::try_new(mu, sigma) {
+            Ok(distribution) => distribution,
+            Err(error) => panic!("{}", error),
+        }
+    }
+
+    /// Create a multivariate Gaussian distribution with mean vector `mu` and
+    /// covariance matrix `sigma` or fail if the parameters are invalid.
+    ///
+    /// The upper triangle of the covariance matrix is filled in from the lower
+    /// one.
+    pub fn try_new(mu: &[f64], sigma: &[f64]) -> Result<Self, Error> {
+        use std::f64::consts::PI;
+
+        let d = mu.len();
+        if d == 0 {
+            return Err(Error::Empty("mu"));
+        }
+        if sigma.len() != d * d {
+            return Err(Error::Mismatch("mu", "sigma"));
+        }
+        for &mu in mu {
+            error::finite(mu, "mu")?;
+        }
+        let mut sigma = sigma.to_vec();
+        for i in 0..d {
+            for j in 0..(i + 1) {
+                error::finite(sigma[i * d + j], "sigma")?;
+                sigma[j * d + i] = sigma[i * d + j];
+            }
+        }
+        let cholesky = match decompose(&sigma, d) {
+            Some(cholesky) => cholesky,
+            _ => return Err(Error::NotPositiveDefinite("sigma")),
+        };
+        let ln_determinant = (0..d).fold(0.0, |sum, i| sum + cholesky[i * d + i].ln());
+        Ok(MultivariateGaussian {
+            d: d,
+            mu: mu.to_vec(),
+            sigma: sigma,
+            cholesky: cholesky,
+            ln_norm: 0.5 * d as f64 * (2.0 * PI).ln() + ln_determinant,
+        })
+    }
+
+    /// Return the number of dimensions.
+    #[inline(always)]
+    pub fn d(&self) -> usize {
+        self.d
+    }
+
+    /// Return the mean vector.
+    #[inline(always)]
+    pub fn mu(&self) -> &[f64] {
+        &self.mu
+    }
+
+    /// Return the covariance matrix in row-major order.
+    #[inline(always)]
+    pub fn sigma(&self) -> &[f64] {
+        &self.sigma
+    }
+
+    /// Return the lower-triangular Cholesky factor of the covariance matrix in
+    /// row-major order.
+    #[inline(always)]
+    pub fn cholesky(&self) -> &[f64] {
+        &self.cholesky
+    }
+
+    /// Compute the conditional distribution of the rest of the components
+    /// given that the components at `indices` are equal to `values` or fail if
+    /// no component remains or the resulting covariance matrix is not positive
+    /// definite due to round-off errors.
+    ///
+    /// It should hold that the indices are distinct and that `values` has the
+    /// same length as `indices`.
+    pub fn conditional(&self, indices: &[usize], values: &[f64]) -> Result<Self, Error> {
+        should!(indices.len() == values.len());
+        let d = self.d;
+        let rest = (0..d).filter(|i| !indices.contains(i)).collect::<Vec<_>>();
+        should!(rest.len() + indices.len() == d);
+        let (m, n) = (rest.len(), indices.len());
+        if n == 0 {
+            return Ok(self.clone());
+        }
+
+        let given = self.marginal(indices)?;
+        let mut residual = (0..n)
+            .map(|i| values[i] - self.mu[indices[i]])
+            .collect::<Vec<_>>();
+        forward(&given.cholesky, n, &mut residual);
+        backward(&given.cholesky, n, &mut residual);
+
+        // Compute the product of the inverse of the covariance of the given
+        // components and their cross-covariance with the rest.
+        let mut gain = vec![0.0; n * m];
+        for j in 0..m {
+            let mut column = (0..n)
+                .map(|i| self.sigma[indices[i] * d + rest[j]])
+                .collect::<Vec<_>>();
+            forward(&given.cholesky, n, &mut column);
+            backward(&given.cholesky, n, &mut column);
+            for i in 0..n {
+                gain[i * m + j] = column[i];
+            }
+        }
+
+        let mut mu = vec![0.0; m];
+        let mut sigma = vec![0.0; m * m];
+        for i in 0..m {
+            mu[i] = self.mu[rest[i]];
+            for k in 0..n {
+                mu[i] += self.sigma[rest[i] * d + indices[k]] * residual[k];
+            }
+            for j in 0..m {
+                let mut value = self.sigma[rest[i] * d + rest[j]];
+                for k in 0..n {
+                    value -= self.sigma[rest[i] * d + indices[k]] * gain[k * m + j];
+                }
+                sigma[i * m + j] = value;
+            }
+        }
+        for i in 0..m {
+            for j in 0..i {
+                let value = 0.5 * (sigma[i * m + j] + sigma[j * m + i]);
+                sigma[i * m + j] = value;
+                sigma[j * m + i] = value;
+            }
+        }
+        MultivariateGaussian::try_new(&mu, &sigma)
+    }
+
+    /// Compute the probability density function.
+    #[inline]
+    pub fn density(&self, x: &[f64]) -> f64 {
+        self.ln_density(x).exp()
+    }
+
+    /// Compute the natural logarithm of the probability density function.
+    pub fn ln_density(&self, x: &[f64]) -> f64 {
+        should!(x.len() == self.d);
+        let mut z = (0..self.d).map(|i| x[i] - self.mu[i]).collect::<Vec<_>>();
+        forward(&self.cholesky, self.d, &mut z);
+        -0.5 * z.iter().fold(0.0, |sum, &z| sum + z * z) - self.ln_norm
+    }
+
+    /// Compute the marginal distribution of the components at `indices` or fail
+    /// if there are no indices.
+    ///
+    /// It should hold that the indices are distinct.
+    pub fn marginal(&self, indices: &[usize]) -> Result<Self, Error> {
+        let (d, n) = (self.d, indices.len());
+        let mu = indices.iter().map(|&i| self.mu[i]).collect::<Vec<_>>();
+        let mut sigma = vec![0.0; n * n];
+        for i in 0..n {
+            for j in 0..n {
+                sigma[i * n + j] = self.sigma[indices[i] * d + indices[j]];
+            }
+        }
+        MultivariateGaussian::try_new(&mu, &sigma)
+    }
+
+    /// Draw a sample and write it into `x`.
+    pub fn sample<S>(&self, source: &mut S, x: &mut [f64])
+    where
+        S: Source,
+    {
+        should!(x.len() == self.d);
+        let d = self.d;
+        let z = (0..d).map(|_| gaussian::sample(source)).collect::<Vec<_>>();
+        for i in 0..d {
+            x[i] = self.mu[i];
+            for j in 0..(i + 1) {
+                x[i] += self.cholesky[i * d + j] * z[j];
+            }
+        }
+    }
+}
+
+// Solve `L^T x = b` in place for a lower-triangular `L`.
+fn backward(l: &[f64], d: usize, b: &mut [f64]) {
+    for i in (0..d).rev() {
+        let mut value = b[i];
+        for j in (i + 1)..d {
+            value -= l[j * d + i] * b[j];
+        }
+        b[i] = value / l[i * d + i];
+    }
+}
+
+// Compute the lower-triangular Cholesky factor of a symmetric matrix, reading
+// only its lower triangle, or return `None` if the matrix is not positive
+// definite.
+fn decompose(a: &[f64], d: usize) -> Option<Vec<f64>> {
+    let mut l = vec![0.0; d * d];
+    for i in 0..d {
+        for j in 0..(i + 1) {
+            let mut value = a[i * d + j];
+            for k in 0..j {
+                value -= l[i * d + k] * l[j * d + k];
+            }
+            if i == j {
+                if value.is_nan() || value <= 0.0 {
+                    return None;
+                }
+                l[i * d + i] = value.sqrt();
+            } else {
+                l[i * d + j] = value / l[j * d + j];
+            }
+        }
+    }
+    Some(l)
+}
+
+// Solve `L x = b` in place for a lower-triangular `L`.
+fn forward(l: &[f64], d: usize, b: &mut [f64]) {
+    for i in 0..d {
+        let mut value = b[i];
+        for j in 0..i {
+            value -= l[i * d + j] * b[j];
+        }
+        b[i] = value / l[i * d + i];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use distribution::Error;
+
+    macro_rules! new(
+        () => (MultivariateGaussian::new(
+            &[1.0, -1.0, 0.5],
+            &[4.0, 1.2, -0.8, 1.2, 2.0, 0.3, -0.8, 0.3, 1.0],
+        ));
+    );
+
+    #[test]
+    fn cholesky() {
+        let d = MultivariateGaussian::new(&[0.0, 0.0], &[4.0, 2.0, 2.0, 5.0]);
+        assert_eq!(d.cholesky(), &[2.0, 0.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn conditional() {
+        let d = new!().conditional(&[2], &[1.0]).unwrap();
+        assert::close(d.mu(), &[0.6, -0.85][..], 1e-14);
+        assert::close(d.sigma(), &[3.36, 1.44, 1.44, 1.91][..], 1e-14);
+
+        let d = new!().conditional(&[0, 2], &[0.0, 1.0]).unwrap();
+        assert::close(d.mu(), &[-1.1071428571428572][..], 1e-14);
+        assert::close(d.sigma(), &[1.2928571428571429][..], 1e-14);
+
+        let d = new!().conditional(&[], &[]).unwrap();
+        assert_eq!((d.mu(), d.sigma()), (new!().mu(), new!().sigma()));
+
+        assert_eq!(
+            new!().conditional(&[1, 2, 0], &[0.0; 3]).unwrap_err(),
+            Error::Empty("mu")
+        );
+    }
+
+    #[test]
+    fn density() {
+        let d = new!();
+        let x = [0.5, 0.2, -0.3];
+        assert::close(d.density(&x), d.ln_density(&x).exp(), 1e-15);
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = new!();
+        let x = vec![[1.0, -1.0, 0.5], [0.5, 0.2, -0.3], [-3.0, 4.0, 2.0]];
+        let p = vec![
+            -3.4912133909298353e+00,
+            -5.4430435014270726e+00,
+            -1.8569482267541254e+01,
+        ];
+        assert::close(
+            &x.iter().map(|x| d.ln_density(x)).collect::<Vec<_>>(),
+            &p,
+            1e-13,
+        );
+
+        let d = MultivariateGaussian::new(&[1.0], &[4.0]);
+        let gaussian = Gaussian::new(1.0, 2.0);
+        assert::close(d.ln_density(&[2.5]), gaussian.ln_density(2.5), 1e-15);
+    }
+
+    #[test]
+    fn marginal() {
+        let d = new!().marginal(&[2, 0]).unwrap();
+        assert_eq!(d.mu(), &[0.5, 1.0]);
+        assert_eq!(d.sigma(), &[1.0, -0.8, -0.8, 4.0]);
+
+        assert_eq!(new!().marginal(&[]).unwrap_err(), Error::Empty("mu"));
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!();
+        let n = 100000;
+        let mut source = source::default();
+        let mut x = [0.0; 3];
+        let mut sum = [0.0; 3];
+        let mut product = [0.0; 9];
+        for _ in 0..n {
+            d.sample(&mut source, &mut x);
+            for i in 0..3 {
+                sum[i] += x[i];
+                for j in 0..3 {
+                    product[i * 3 + j] += x[i] * x[j];
+                }
+            }
+        }
+        for i in 0..3 {
+            let mean = sum[i] / n as f64;
+            assert!((mean - d.mu()[i]).abs() < 0.05);
+            for j in 0..3 {
+                let covariance = product[i * 3 + j] / n as f64 - mean * sum[j] / n as f64;
+                assert!((covariance - d.sigma()[i * 3 + j]).abs() < 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn triangle() {
+        let d = MultivariateGaussian::new(
+            &[1.0, -1.0, 0.5],
+            &[4.0, 0.0, 0.0, 1.2, 2.0, 0.0, -0.8, 0.3, 1.0],
+        );
+        assert_eq!(d.sigma(), new!().sigma());
+
+        let c = d.conditional(&[2], &[1.0]).unwrap();
+        assert::close(c.mu(), &[0.6, -0.85][..], 1e-14);
+        assert::close(c.sigma(), &[3.36, 1.44, 1.44, 1.91][..], 1e-14);
+
+        let m = d.marginal(&[2, 0]).unwrap();
+        assert_eq!(m.mu(), &[0.5, 1.0]);
+        assert_eq!(m.sigma(), &[1.0, -0.8, -0.8, 4.0]);
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(
+            MultivariateGaussian::try_new(&[], &[]).unwrap_err(),
+            Error::Empty("mu")
+        );
+        assert_eq!(
+            MultivariateGaussian::try_new(&[0.0, 0.0], &[1.0]).unwrap_err(),
+            Error::Mismatch("mu", "sigma")
+        );
+        assert_eq!(
+            MultivariateGaussian::try_new(&[0.0, 0.0], &[1.0, 2.0, 2.0, 1.0]).unwrap_err(),
+            Error::NotPositiveDefinite("sigma")
+        );
+    }
+}
@@ -0,0 +1,734 @@
+use std::{error, fmt, str};
+
+use distribution::{
+    AnyDistribution, Bernoulli, Beta, Binomial, Categorical, ChiSquared, Dirichlet, Error,
+    Exponential, FisherSnedecor, Gamma, Gaussian, Laplace, Logistic, Lognormal, Multinomial,
+    MultivariateGaussian, Pert, Poisson, StudentsT, Triangular, Uniform, WeightedCategorical,
+};
+
+/// An error in parsing a distribution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The byte offset in the input at which the error occurs.
+    pub position: usize,
+    /// The kind of the error.
+    pub kind: ParseErrorKind,
+}
+
+/// The kind of an error in parsing a distribution.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// The number of parameters is not the one given.
+    Count(usize),
+    /// The input does not contain what is described.
+    Expected(&'static str),
+    /// The parameters are invalid.
+    Parameter(Error),
+    /// The name of the distribution is unknown.
+    Unknown,
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Integer,
+    Number,
+    Vector,
+}
+
+struct Argument {
+    position: usize,
+    value: Value,
+}
+
+struct Parser<'l> {
+    input: &'l str,
+    position: usize,
+}
+
+// A parameter that can be written in the notation.
+trait Parameter {
+    fn write(&self, formatter: &mut fmt::Formatter) -> fmt::Result;
+}
+
+enum Value {
+    Number(f64),
+    Vector(Vec<f64>),
+}
+
+macro_rules! implement(
+    ($($kind:ident($($field:ident),*),)*) => ($(
+        impl fmt::Display for $kind {
+            fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                let parameters: &[&dyn Parameter] = &[$(&self.$field()),*];
+                formatter.write_str(concat!(stringify!($kind), "("))?;
+                for (i, parameter) in parameters.iter().enumerate() {
+                    if i > 0 {
+                        formatter.write_str(", ")?;
+                    }
+                    parameter.write(formatter)?;
+                }
+                formatter.write_str(")")
+            }
+        }
+    )*);
+);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        match self.kind {
+            ParseErrorKind::Count(count) => write!(formatter, "expected {} parameters", count)?,
+            ParseErrorKind::Expected(what) => write!(formatter, "expected {}", what)?,
+            ParseErrorKind::Parameter(ref error) => write!(formatter, "{}", error)?,
+            ParseErrorKind::Unknown => write!(formatter, "unknown distribution")?,
+        }
+        write!(formatter, " at position {}", self.position)
+    }
+}
+
+impl error::Error for ParseError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self.kind {
+            ParseErrorKind::Parameter(ref error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl str::FromStr for AnyDistribution {
+    type Err = ParseError;
+
+    /// Parse a distribution written as its name followed by its parameters in
+    /// parentheses, such as `Gaussian(0, 1)` or `Categorical([0.25, 0.75])`.
+    ///
+    /// The parameters are given in the order of the arguments of the
+    /// corresponding constructor.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        use self::Kind::*;
+
+        let (position, name, arguments, end) = parse(input)?;
+        let build = |kinds: &[(&'static str, Kind)], constructor: &dyn Fn(&[Argument]) -> _| {
+            build(&arguments, end, kinds, constructor)
+        };
+        match name {
+            "Bernoulli" => build(&[("p", Number)], &|x| {
+                Bernoulli::try_new(x[0].number()).map(AnyDistribution::from)
+            }),
+            "Beta" => build(
+                &[
+                    ("alpha", Number),
+                    ("beta", Number),
+                    ("a", Number),
+                    ("b", Number),
+                ],
+                &|x| {
+                    Beta::try_new(x[0].number(), x[1].number(), x[2].number(), x[3].number())
+                        .map(AnyDistribution::from)
+                },
+            ),
+            "Binomial" => build(&[("n", Integer), ("p", Number)], &|x| {
+                Binomial::try_new(x[0].integer(), x[1].number()).map(AnyDistribution::from)
+            }),
+            "Categorical" => build(&[("p", Vector)], &|x| {
+                Categorical::try_new(x[0].vector()).map(AnyDistribution::from)
+            }),
+            "ChiSquared" => build(&[("k", Number)], &|x| {
+                ChiSquared::try_new(x[0].number()).map(AnyDistribution::from)
+            }),
+            "Exponential" => build(&[("lambda", Number)], &|x| {
+                Exponential::try_new(x[0].number()).map(AnyDistribution::from)
+            }),
+            "FisherSnedecor" => build(&[("d1", Number), ("d2", Number)], &|x| {
+                FisherSnedecor::try_new(x[0].number(), x[1].number()).map(AnyDistribution::from)
+            }),
+            "Gamma" => build(&[("k", Number), ("theta", Number)], &|x| {
+                Gamma::try_new(x[0].number(), x[1].number()).map(AnyDistribution::from)
+            }),
+            "Gaussian" => build(&[("mu", Number), ("sigma", Number)], &|x| {
+                Gaussian::try_new(x[0].number(), x[1].number()).map(AnyDistribution::from)
+            }),
+            "Laplace" => build(&[("mu", Number), ("b", Number)], &|x| {
+                Laplace::try_new(x[0].number(), x[1].number()).map(AnyDistribution::from)
+            }),
+            "Logistic" => build(&[("mu", Number), ("s", Number)], &|x| {
+                Logistic::try_new(x[0].number(), x[1].number()).map(AnyDistribution::from)
+            }),
+            "Lognormal" => build(&[("mu", Number), ("sigma", Number)], &|x| {
+                Lognormal::try_new(x[0].number(), x[1].number()).map(AnyDistribution::from)
+            }),
+            "Pert" => build(&[("a", Number), ("b", Number), ("c", Number)], &|x| {
+                Pert::try_new(x[0].number(), x[1].number(), x[2].number())
+                    .map(AnyDistribution::from)
+            }),
+            "Poisson" => build(&[("lambda", Number)], &|x| {
+                Poisson::try_new(x[0].number()).map(AnyDistribution::from)
+            }),
+            "StudentsT" => build(&[("nu", Number), ("mu", Number), ("sigma", Number)], &|x| {
+                StudentsT::try_new(x[0].number(), x[1].number(), x[2].number())
+                    .map(AnyDistribution::from)
+            }),
+            "Triangular" => build(&[("a", Number), ("b", Number), ("c", Number)], &|x| {
+                Triangular::try_new(x[0].number(), x[1].number(), x[2].number())
+                    .map(AnyDistribution::from)
+            }),
+            "Uniform" => build(&[("a", Number), ("b", Number)], &|x| {
+                Uniform::try_new(x[0].number(), x[1].number()).map(AnyDistribution::from)
+            }),
+            _ => Err(unknown(position)),
+        }
+    }
+}
+
+impl str::FromStr for Dirichlet {
+    type Err = ParseError;
+
+    /// Parse the distribution written as `Dirichlet([alpha, ...])`.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let (position, name, arguments, end) = parse(input)?;
+        if name != "Dirichlet" {
+            return Err(unknown(position));
+        }
+        build(&arguments, end, &[("alpha", Kind::Vector)], &|x| {
+            Dirichlet::try_new(x[0].vector())
+        })
+    }
+}
+
+impl str::FromStr for Multinomial {
+    type Err = ParseError;
+
+    /// Parse the distribution written as `Multinomial(n, [p, ...])`.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let (position, name, arguments, end) = parse(input)?;
+        if name != "Multinomial" {
+            return Err(unknown(position));
+        }
+        build(
+            &arguments,
+            end,
+            &[("n", Kind::Integer), ("p", Kind::Vector)],
+            &|x| Multinomial::try_new(x[0].integer(), x[1].vector()),
+        )
+    }
+}
+
+impl str::FromStr for MultivariateGaussian {
+    type Err = ParseError;
+
+    /// Parse the distribution written as `MultivariateGaussian([mu, ...],
+    /// [sigma, ...])`, where the covariance matrix is given in row-major
+    /// order.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let (position, name, arguments, end) = parse(input)?;
+        if name != "MultivariateGaussian" {
+            return Err(unknown(position));
+        }
+        build(
+            &arguments,
+            end,
+            &[("mu", Kind::Vector), ("sigma", Kind::Vector)],
+            &|x| MultivariateGaussian::try_new(x[0].vector(), x[1].vector()),
+        )
+    }
+}
+
+impl str::FromStr for WeightedCategorical {
+    type Err = ParseError;
+
+    /// Parse the distribution written as `WeightedCategorical([weight, ...])`.
+    fn from_str(input: &str) -> Result<Self, ParseError> {
+        let (position, name, arguments, end) = parse(input)?;
+        if name != "WeightedCategorical" {
+            return Err(unknown(position));
+        }
+        build(&arguments, end, &[("weights", Kind::Vector)], &|x| {
+            WeightedCategorical::try_new(x[0].vector())
+        })
+    }
+}
+
+impl Argument {
+    fn integer(&self) -> usize {
+        self.number() as usize
+    }
+
+    fn number(&self) -> f64 {
+        match self.value {
+            Value::Number(value) => value,
+            _ => unreachable!(),
+        }
+    }
+
+    fn vector(&self) -> &[f64] {
+        match self.value {
+            Value::Vector(ref values) => values,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Parameter for f64 {
+    #[inline]
+    fn write(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self)
+    }
+}
+
+impl Parameter for usize {
+    #[inline]
+    fn write(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "{}", self)
+    }
+}
+
+impl Parameter for &[f64] {
+    fn write(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("[")?;
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                formatter.write_str(", ")?;
+            }
+            write!(formatter, "{}", value)?;
+        }
+        formatter.write_str("]")
+    }
+}
+
+impl<'l> Parser<'l> {
+    fn arguments(&mut self) -> Result<(Vec<Argument>, usize), ParseError> {
+        self.expect('(')?;
+        let mut arguments = Vec::new();
+        if self.peek() != Some(')') {
+            loop {
+                let position = self.position;
+                let value = if self.peek() == Some('[') {
+                    Value::Vector(self.vector()?)
+                } else {
+                    Value::Number(self.number()?)
+                };
+                arguments.push(Argument {
+                    position: position,
+                    value: value,
+                });
+                if self.peek() != Some(',') {
+                    break;
+                }
+                self.advance();
+            }
+        }
+        let end = self.position;
+        self.expect(')')?;
+        Ok((arguments, end))
+    }
+
+    fn advance(&mut self) {
+        if let Some(c) = self.peek() {
+            self.position += c.len_utf8();
+        }
+        self.skip();
+    }
+
+    fn end(&mut self) -> Result<(), ParseError> {
+        match self.peek() {
+            Some(_) => Err(self.error("the end of input")),
+            _ => Ok(()),
+        }
+    }
+
+    fn error(&self, what: &'static str) -> ParseError {
+        ParseError {
+            position: self.position,
+            kind: ParseErrorKind::Expected(what),
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.peek() == Some(c) {
+            self.advance();
+            return Ok(());
+        }
+        Err(self.error(match c {
+            '(' => "`(`",
+            ')' => "`)`",
+            _ => "`]`",
+        }))
+    }
+
+    fn name(&mut self) -> Result<(usize, &'l str), ParseError> {
+        self.skip();
+        let start = self.position;
+        let name = self.token(|c| c.is_alphanumeric() || c == '_');
+        if name.is_empty() || !name.starts_with(char::is_alphabetic) {
+            return Err(ParseError {
+                position: start,
+                kind: ParseErrorKind::Expected("a name"),
+            });
+        }
+        self.skip();
+        Ok((start, name))
+    }
+
+    fn number(&mut self) -> Result<f64, ParseError> {
+        let start = self.position;
+        let token = self.token(|c| c.is_alphanumeric() || c == '.' || c == '+' || c == '-');
+        match token.parse() {
+            Ok(value) => {
+                self.skip();
+                Ok(value)
+            }
+            _ => Err(ParseError {
+                position: start,
+                kind: ParseErrorKind::Expected("a number"),
+            }),
+        }
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    fn skip(&mut self) {
+        let rest = &self.input[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn token<F>(&mut self, accept: F) -> &'l str
+    where
+        F: Fn(char) -> bool,
+    {
+        let rest = &self.input[self.position..];
+        let length = rest.find(|c| !accept(c)).unwrap_or(rest.len());
+        self.position += length;
+        &rest[..length]
+    }
+
+    fn vector(&mut self) -> Result<Vec<f64>, ParseError> {
+        self.advance();
+        let mut values = Vec::new();
+        if self.peek() != Some(']') {
+            loop {
+                values.push(self.number()?);
+                if self.peek() != Some(',') {
+                    break;
+                }
+                self.advance();
+            }
+        }
+        self.expect(']')?;
+        Ok(values)
+    }
+}
+
+implement! {
+    Bernoulli(p),
+    Beta(alpha, beta, a, b),
+    Binomial(n, p),
+    Categorical(p),
+    ChiSquared(k),
+    Dirichlet(alpha),
+    Exponential(lambda),
+    FisherSnedecor(d1, d2),
+    Gamma(k, theta),
+    Gaussian(mu, sigma),
+    Laplace(mu, b),
+    Logistic(mu, s),
+    Lognormal(mu, sigma),
+    Multinomial(n, p),
+    MultivariateGaussian(mu, sigma),
+    Pert(a, b, c),
+    Poisson(lambda),
+    StudentsT(nu, mu, sigma),
+    Triangular(a, b, c),
+    Uniform(a, b),
+    WeightedCategorical(weights),
+}
+
+// Check the arguments against the expected kinds and construct the
+// distribution, reporting invalid parameters at the position of the
+// corresponding argument.
+fn build<T>(
+    arguments: &[Argument],
+    end: usize,
+    kinds: &[(&'static str, Kind)],
+    constructor: &dyn Fn(&[Argument]) -> Result<T, Error>,
+) -> Result<T, ParseError> {
+    if arguments.len() != kinds.len() {
+        return Err(ParseError {
+            position: arguments
+                .get(kinds.len())
+                .map_or(end, |argument| argument.position),
+            kind: ParseErrorKind::Count(kinds.len()),
+        });
+    }
+    for (argument, &(_, kind)) in arguments.iter().zip(kinds) {
+        let what = match (kind, &argument.value) {
+            (Kind::Integer, &Value::Number(value)) => {
+                if value >= 0.0 && value.fract() == 0.0 && value <= ::std::usize::MAX as f64 {
+                    continue;
+                }
+                "a nonnegative integer"
+            }
+            (Kind::Number, &Value::Number(_)) | (Kind::Vector, &Value::Vector(_)) => continue,
+            (Kind::Vector, _) => "a vector",
+            _ => "a number",
+        };
+        return Err(ParseError {
+            position: argument.position,
+            kind: ParseErrorKind::Expected(what),
+        });
+    }
+    constructor(arguments).map_err(|error| {
+        let name = match error {
+            Error::Empty(name)
+            | Error::Mismatch(name, _)
+            | Error::Negative(name)
+            | Error::NotFinite(name)
+            | Error::NotLess(name, _)
+            | Error::NotLessOrEqual(name, _)
+            | Error::NotNormalized(name)
+            | Error::NotPositive(name)
+            | Error::NotPositiveDefinite(name)
+            | Error::NotProbability(name)
+            | Error::Zero(name) => name,
+        };
+        let position = kinds
+            .iter()
+            .position(|&(other, _)| other == name)
+            .map_or(arguments[0].position, |i| arguments[i].position);
+        ParseError {
+            position: position,
+            kind: ParseErrorKind::Parameter(error),
+        }
+    })
+}
+
+// Split the input into the name of the distribution, its arguments, and the
+// position of the closing parenthesis.
+fn parse(input: &str) -> Result<(usize, &str, Vec<Argument>, usize), ParseError> {
+    let mut parser = Parser {
+        input: input,
+        position: 0,
+    };
+    let (position, name) = parser.name()?;
+    let (arguments, end) = parser.arguments()?;
+    parser.end()?;
+    Ok((position, name, arguments, end))
+}
+
+#[inline]
+fn unknown(position: usize) -> ParseError {
+    ParseError {
+        position: position,
+        kind: ParseErrorKind::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+
+    use distribution::{Error, ParseError, ParseErrorKind};
+
+    macro_rules! error(
+        ($input:expr) => ($input.parse::<AnyDistribution>().unwrap_err());
+    );
+
+    #[test]
+    fn display() {
+        assert_eq!(Gaussian::new(0.0, 1.0).to_string(), "Gaussian(0, 1)");
+        assert_eq!(Binomial::new(100, 0.3).to_string(), "Binomial(100, 0.3)");
+        assert_eq!(Pert::new(1.0, 3.0, 8.0).to_string(), "Pert(1, 3, 8)");
+        assert_eq!(
+            Beta::new(2.0, 5.0, 0.0, 1.0).to_string(),
+            "Beta(2, 5, 0, 1)"
+        );
+        assert_eq!(
+            Categorical::new(&[0.25, 0.75]).to_string(),
+            "Categorical([0.25, 0.75])"
+        );
+        assert_eq!(
+            Multinomial::new(10, &[0.5, 0.5]).to_string(),
+            "Multinomial(10, [0.5, 0.5])"
+        );
+        assert_eq!(
+            AnyDistribution::from(Uniform::new(-1.5, 2.0)).to_string(),
+            "Uniform(-1.5, 2)"
+        );
+    }
+
+    #[test]
+    fn from_str() {
+        let d: AnyDistribution = " Beta( 2,5 , 0, 1e1 ) ".parse().unwrap();
+        assert_eq!(d.to_string(), "Beta(2, 5, 0, 10)");
+
+        let d: AnyDistribution = "Categorical([0.25, 0.75])".parse().unwrap();
+        assert!(d.is_discrete());
+        assert_eq!(d.distribution(0.0), 0.25);
+
+        let inputs = [
+            "Bernoulli(0.3)",
+            "Binomial(100, 0.3)",
+            "ChiSquared(3)",
+            "Exponential(0.1)",
+            "FisherSnedecor(2, 3)",
+            "Gamma(2, 0.5)",
+            "Gaussian(0.1, 0.7)",
+            "Laplace(-1, 2)",
+            "Logistic(0, 1)",
+            "Lognormal(0, 0.25)",
+            "Pert(1, 3, 8)",
+            "Poisson(4.2)",
+            "StudentsT(3, 0, 1)",
+            "Triangular(0, 3, 1)",
+        ];
+        for input in &inputs {
+            let d: AnyDistribution = input.parse().unwrap();
+            assert_eq!(&d.to_string(), input);
+        }
+
+        let d = AnyDistribution::from(Gaussian::new(0.1 + 0.2, 1.0 / 3.0));
+        let e: AnyDistribution = d.to_string().parse().unwrap();
+        assert_eq!(d.mean(), e.mean());
+        assert_eq!(d.variance(), e.variance());
+    }
+
+    #[test]
+    fn round_trip() {
+        let distributions = [
+            AnyDistribution::from(Bernoulli::new(0.3)),
+            AnyDistribution::from(Beta::new(2.0, 5.0, -1.0, 1.5)),
+            AnyDistribution::from(Binomial::new(100, 0.3)),
+            AnyDistribution::from(Categorical::new(&[0.1, 0.2, 0.7])),
+            AnyDistribution::from(ChiSquared::new(3.0)),
+            AnyDistribution::from(Exponential::new(0.1)),
+            AnyDistribution::from(FisherSnedecor::new(2.0, 3.0)),
+            AnyDistribution::from(Gamma::new(2.0, 0.5)),
+            AnyDistribution::from(Gaussian::new(0.1 + 0.2, 1.0 / 3.0)),
+            AnyDistribution::from(Laplace::new(-1.0, 2.0)),
+            AnyDistribution::from(Logistic::new(0.0, 1e-3)),
+            AnyDistribution::from(Lognormal::new(0.0, 0.25)),
+            AnyDistribution::from(Pert::new(1.0, 3.0, 8.0)),
+            AnyDistribution::from(Poisson::new(4.2)),
+            AnyDistribution::from(StudentsT::new(3.0, 0.0, 1.0)),
+            AnyDistribution::from(Triangular::new(0.0, 3.0, 1.0)),
+            AnyDistribution::from(Uniform::new(-1.5, 2.0)),
+        ];
+        for d in &distributions {
+            let e: AnyDistribution = d.to_string().parse().unwrap();
+            assert_eq!(e.to_string(), d.to_string());
+        }
+
+        let d = Dirichlet::new(&[0.5, 1.0, 2.5]);
+        let e: Dirichlet = d.to_string().parse().unwrap();
+        assert_eq!(e.alpha(), d.alpha());
+
+        let d = Multinomial::new(10, &[0.25, 0.75]);
+        let e: Multinomial = d.to_string().parse().unwrap();
+        assert_eq!((e.n(), e.p()), (d.n(), d.p()));
+
+        let d = MultivariateGaussian::new(&[0.5, 1.0], &[2.0, 0.3, 0.3, 1.0 / 3.0]);
+        let e: MultivariateGaussian = d.to_string().parse().unwrap();
+        assert_eq!((e.mu(), e.sigma()), (d.mu(), d.sigma()));
+
+        let d = WeightedCategorical::new(&[1.0, 0.0, 3.5]);
+        let e: WeightedCategorical = d.to_string().parse().unwrap();
+        assert_eq!(e.weights(), d.weights());
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            "Gaussian(0, 1)".parse::<Dirichlet>().unwrap_err(),
+            ParseError {
+                position: 0,
+                kind: ParseErrorKind::Unknown,
+            }
+        );
+        assert_eq!(
+            "Multinomial(10, [0.5, 0.6])"
+                .parse::<Multinomial>()
+                .unwrap_err(),
+            ParseError {
+                position: 16,
+                kind: ParseErrorKind::Parameter(Error::NotNormalized("p")),
+            }
+        );
+        assert_eq!(
+            "MultivariateGaussian([0, 0], [1, 0, 0])"
+                .parse::<MultivariateGaussian>()
+                .unwrap_err(),
+            ParseError {
+                position: 21,
+                kind: ParseErrorKind::Parameter(Error::Mismatch("mu", "sigma")),
+            }
+        );
+
+        assert_eq!(
+            error!("Gaussian(0, -1)"),
+            ParseError {
+                position: 12,
+                kind: ParseErrorKind::Parameter(Error::NotPositive("sigma")),
+            }
+        );
+        assert_eq!(
+            error!("Gaussian(0)"),
+            ParseError {
+                position: 10,
+                kind: ParseErrorKind::Count(2),
+            }
+        );
+        assert_eq!(
+            error!("Poisson(1, 2)"),
+            ParseError {
+                position: 11,
+                kind: ParseErrorKind::Count(1),
+            }
+        );
+        assert_eq!(
+            error!("Binomial(2.5, 0.5)"),
+            ParseError {
+                position: 9,
+                kind: ParseErrorKind::Expected("a nonnegative integer"),
+            }
+        );
+        assert_eq!(
+            error!("Categorical(0.5)"),
+            ParseError {
+                position: 12,
+                kind: ParseErrorKind::Expected("a vector"),
+            }
+        );
+        assert_eq!(
+            error!("Uniform(0, x)"),
+            ParseError {
+                position: 11,
+                kind: ParseErrorKind::Expected("a number"),
+            }
+        );
+        assert_eq!(
+            error!("Normal(0, 1)"),
+            ParseError {
+                position: 0,
+                kind: ParseErrorKind::Unknown,
+            }
+        );
+        assert_eq!(
+            error!("Gaussian(0, 1"),
+            ParseError {
+                position: 13,
+                kind: ParseErrorKind::Expected("`)`"),
+            }
+        );
+        assert_eq!(
+            error!("Gaussian(0, 1) x"),
+            ParseError {
+                position: 15,
+                kind: ParseErrorKind::Expected("the end of input"),
+            }
+        );
+        assert_eq!(
+            error!("Uniform(1, 0)").to_string(),
+            "`a` should be less than `b` at position 8"
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, Error};
 use source::Source;
 
 /// A PERT distribution.
@@ -32,6 +32,15 @@ impl Pert {
         }
     }
 
+    /// Create a PERT distribution with parameters `a`, `b`, and `c` or fail if
+    /// the parameters are invalid.
+    #[inline]
+    pub fn try_new(a: f64, b: f64, c: f64) -> Result<Self, Error> {
+        error::less(a, b, ("a", "b"))?;
+        error::less(b, c, ("b", "c"))?;
+        Ok(Pert::new(a, b, c))
+    }
+
     /// Return the first parameter.
     #[inline(always)]
     pub fn a(&self) -> f64 {
@@ -74,6 +83,18 @@ impl distribution::Continuous for Pert {
                 / scale
         }
     }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        if x < self.a || x > self.c {
+            ::std::f64::NEG_INFINITY
+        } else {
+            let scale = self.c - self.a;
+            let x = (x - self.a) / scale;
+            (self.alpha - 1.0) * x.ln() + (self.beta - 1.0) * (-x).ln_1p()
+                - self.ln_beta
+                - scale.ln()
+        }
+    }
 }
 
 impl distribution::Distribution for Pert {
@@ -89,6 +110,27 @@ impl distribution::Distribution for Pert {
             ((x - self.a) / (self.c - self.a)).inc_beta(self.alpha, self.beta, self.ln_beta)
         }
     }
+
+    fn ln_distribution(&self, x: f64) -> f64 {
+        if x <= self.a {
+            ::std::f64::NEG_INFINITY
+        } else if x >= self.c {
+            0.0
+        } else {
+            self.distribution(x).ln()
+        }
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        use special::Beta;
+        if x <= self.a {
+            1.0
+        } else if x >= self.c {
+            0.0
+        } else {
+            ((self.c - x) / (self.c - self.a)).inc_beta(self.beta, self.alpha, self.ln_beta)
+        }
+    }
 }
 
 impl distribution::Entropy for Pert {
@@ -109,6 +151,13 @@ impl distribution::Inverse for Pert {
         should!(0.0 <= p && p <= 1.0);
         self.a + (self.c - self.a) * p.inv_inc_beta(self.alpha, self.beta, self.ln_beta)
     }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        use special::Beta;
+        should!(0.0 <= q && q <= 1.0);
+        self.c - (self.c - self.a) * q.inv_inc_beta(self.beta, self.alpha, self.ln_beta)
+    }
 }
 
 impl distribution::Kurtosis for Pert {
@@ -273,11 +322,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(-1.0, 0.5, 2.0);
+        for &q in &[0.0, 0.001, 0.1, 0.5, 0.9, 0.999, 1.0] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-12);
+        }
+    }
+
     #[test]
     fn kurtosis() {
         assert::close(new!(0.0, 0.5, 1.0).kurtosis(), -2.0 / 3.0, 1e-14);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(-1.0, 0.5, 2.0);
+        for &x in &[-1.5, -1.0, -0.5, 0.0, 0.5, 1.5, 2.5] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(-1.0, 0.5, 2.0);
+        for &x in &[-1.5, -1.0, -0.5, 0.0, 0.5, 1.5, 2.5] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(-1.0, 0.5, 2.0);
+        for &x in &[-1.5, -1.0, -0.5, 0.0, 0.5, 1.5, 2.5] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-14);
+        }
+    }
+
     #[test]
     fn mean() {
         assert::close(new!(0.0, 0.5, 1.0).mean(), 0.5, 1e-14);
@@ -318,6 +399,25 @@ mod tests {
         assert::close(new!(-1.0, 0.8, 2.0).skewness(), -0.17797249266332246, 1e-14);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(-1.0, 0.5, 2.0);
+        for &x in &[-1.5, -1.0, 0.0, 0.5, 1.5, 2.0, 2.5] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-14);
+        }
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Pert::try_new(-1.0, 0.5, 2.0).is_ok());
+        assert_eq!(
+            Pert::try_new(-1.0, 2.0, 2.0).unwrap_err(),
+            Error::NotLess("b", "c")
+        );
+    }
+
     #[test]
     fn variance() {
         assert::close(new!(0.0, 0.5, 1.0).variance(), 0.25 / 7.0, 1e-14);
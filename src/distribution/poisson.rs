@@ -0,0 +1,546 @@
+use distribution::{self, error, fit, Error, Estimate, FitError};
+use source::Source;
+
+/// A Poisson distribution.
+#[derive(Clone, Copy, Debug)]
+pub struct Poisson {
+    lambda: f64,
+}
+
+impl Poisson {
+    /// Create a Poisson distribution with rate `lambda`.
+    ///
+    /// It should hold that `lambda > 0`.
+    #[inline]
+    pub fn new(lambda: f64) -> Self {
+        should!(lambda > 0.0);
+        Poisson { lambda: lambda }
+    }
+
+    /// Create a Poisson distribution with rate `lambda` or fail if the parameter
+    /// is invalid.
+    #[inline]
+    pub fn try_new(lambda: f64) -> Result<Self, Error> {
+        error::positive(lambda, "lambda")?;
+        Ok(Poisson::new(lambda))
+    }
+
+    /// Return the rate parameter.
+    #[inline(always)]
+    pub fn lambda(&self) -> f64 {
+        self.lambda
+    }
+}
+
+impl distribution::Discrete for Poisson {
+    /// Compute the probability mass function.
+    ///
+    /// A saddle-point expansion is used for accurate computation for large
+    /// `lambda`.
+    ///
+    /// ## References
+    ///
+    /// 1. C. Loader, “Fast and Accurate Computation of Binomial Probabilities,”
+    ///    2000.
+    #[inline]
+    fn mass(&self, x: usize) -> f64 {
+        self.ln_mass(x).exp()
+    }
+
+    fn ln_mass(&self, x: usize) -> f64 {
+        use distribution::binomial::{ln_d0, stirlerr};
+        use std::f64::consts::PI;
+
+        if x == 0 {
+            -self.lambda
+        } else {
+            let x = x as f64;
+            -stirlerr(x) - ln_d0(x, self.lambda) - 0.5 * (2.0 * PI * x).ln()
+        }
+    }
+}
+
+impl distribution::Distribution for Poisson {
+    type Value = usize;
+
+    /// Compute the cumulative distribution function.
+    ///
+    /// The implementation is based on the incomplete gamma function.
+    fn distribution(&self, x: f64) -> f64 {
+        use special::Gamma;
+        if x < 0.0 {
+            return 0.0;
+        }
+        1.0 - self.lambda.inc_gamma(x.floor() + 1.0)
+    }
+
+    fn ln_distribution(&self, x: f64) -> f64 {
+        use distribution::gamma;
+        if x < 0.0 {
+            return ::std::f64::NEG_INFINITY;
+        }
+        gamma::ln_survival(self.lambda, x.floor() + 1.0)
+    }
+
+    fn ln_survival(&self, x: f64) -> f64 {
+        use distribution::gamma;
+        if x < 0.0 {
+            return 0.0;
+        }
+        gamma::ln_distribution(self.lambda, x.floor() + 1.0)
+    }
+
+    /// Compute the survival function.
+    ///
+    /// The implementation is based on the incomplete gamma function.
+    fn survival(&self, x: f64) -> f64 {
+        use distribution::gamma;
+        if x < 0.0 {
+            return 1.0;
+        }
+        gamma::distribution(self.lambda, x.floor() + 1.0)
+    }
+}
+
+impl distribution::Entropy for Poisson {
+    fn entropy(&self) -> f64 {
+        use distribution::Discrete;
+        use std::f64::consts::{E, PI};
+
+        let lambda = self.lambda;
+        if lambda > 1e6 {
+            // Use an asymptotic expansion.
+            0.5 * (2.0 * PI * E * lambda).ln()
+                - 1.0 / (12.0 * lambda)
+                - 1.0 / (24.0 * lambda * lambda)
+                - 19.0 / (360.0 * lambda * lambda * lambda)
+        } else {
+            let spread = 20.0 * lambda.sqrt() + 20.0;
+            let start = (lambda - spread).max(0.0) as usize;
+            let end = (lambda + spread) as usize;
+            -(start..(end + 1)).fold(0.0, |sum, i| {
+                let p = self.mass(i);
+                if p > 0.0 {
+                    sum + p * p.ln()
+                } else {
+                    sum
+                }
+            })
+        }
+    }
+}
+
+impl distribution::Fit for Poisson {
+    fn fit(data: &[usize]) -> Result<Estimate<Self>, FitError> {
+        if data.is_empty() {
+            return Err(FitError::Insufficient);
+        }
+        let lambda = fit::mean(data.iter().map(|&x| x as f64));
+        Ok(fit::discrete(Poisson::try_new(lambda)?, data, 0, true))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    ///
+    /// The estimate coincides with the maximum-likelihood one.
+    #[inline]
+    fn fit_moments(data: &[usize]) -> Result<Estimate<Self>, FitError> {
+        Self::fit(data)
+    }
+}
+
+impl distribution::Inverse for Poisson {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The search starts at a Cornish–Fisher approximation and proceeds by
+    /// summing up the mass function.
+    fn inverse(&self, p: f64) -> usize {
+        use distribution::gaussian;
+        use distribution::{Discrete, Distribution};
+
+        should!(0.0 <= p && p <= 1.0);
+
+        if p == 0.0 {
+            return 0;
+        }
+        if p == 1.0 {
+            return ::std::usize::MAX;
+        }
+
+        let w = gaussian::inverse(p);
+        let guess = self.lambda + self.lambda.sqrt() * w + (w * w - 1.0) / 6.0;
+        let mut k = guess.round().max(0.0) as usize;
+        let mut sum = self.distribution(k as f64);
+        if sum < p {
+            loop {
+                k += 1;
+                let mass = self.mass(k);
+                sum += mass;
+                if sum >= p || (mass == 0.0 && k as f64 > self.lambda) {
+                    return k;
+                }
+            }
+        }
+        while k > 0 {
+            let previous = sum - self.mass(k);
+            if previous < p {
+                break;
+            }
+            sum = previous;
+            k -= 1;
+        }
+        k
+    }
+
+    /// Compute the inverse of the survival function.
+    ///
+    /// The survival function is inverted by bisection, which retains accuracy
+    /// in the upper tail.
+    fn inverse_survival(&self, q: f64) -> usize {
+        use distribution::Distribution;
+
+        should!(0.0 <= q && q <= 1.0);
+
+        if q == 0.0 {
+            return ::std::usize::MAX;
+        }
+        if self.survival(0.0) <= q {
+            return 0;
+        }
+        let (mut lower, mut upper) = (0, (self.lambda.ceil() as usize).max(1));
+        while self.survival(upper as f64) > q {
+            lower = upper;
+            upper *= 2;
+        }
+        while upper - lower > 1 {
+            let middle = lower + (upper - lower) / 2;
+            if self.survival(middle as f64) > q {
+                lower = middle;
+            } else {
+                upper = middle;
+            }
+        }
+        upper
+    }
+}
+
+impl distribution::Kurtosis for Poisson {
+    #[inline]
+    fn kurtosis(&self) -> f64 {
+        self.lambda.recip()
+    }
+}
+
+impl distribution::Mean for Poisson {
+    #[inline]
+    fn mean(&self) -> f64 {
+        self.lambda
+    }
+}
+
+impl distribution::Median for Poisson {
+    fn median(&self) -> f64 {
+        use distribution::Inverse;
+        self.inverse(0.5) as f64
+    }
+}
+
+impl distribution::Modes for Poisson {
+    fn modes(&self) -> Vec<usize> {
+        let lambda = self.lambda;
+        if lambda.fract() == 0.0 {
+            vec![lambda as usize - 1, lambda as usize]
+        } else {
+            vec![lambda.floor() as usize]
+        }
+    }
+}
+
+impl distribution::Sample for Poisson {
+    /// Draw a sample.
+    ///
+    /// For small `lambda`, the inverse of the distribution function is
+    /// computed by sequential search. Otherwise, the transformed rejection
+    /// method with squeeze (PTRS) is used.
+    ///
+    /// ## References
+    ///
+    /// 1. W. Hörmann, “The transformed rejection method for generating Poisson
+    ///    random variables,” Insurance: Mathematics and Economics, vol. 12,
+    ///    no. 1, pp. 39–45, 1993.
+    fn sample<S>(&self, source: &mut S) -> usize
+    where
+        S: Source,
+    {
+        use special::Gamma;
+
+        let lambda = self.lambda;
+        if lambda < 10.0 {
+            let u = source.read::<f64>();
+            let mut k = 0;
+            let mut mass = (-lambda).exp();
+            let mut sum = mass;
+            while u > sum {
+                k += 1;
+                mass *= lambda / k as f64;
+                let next = sum + mass;
+                if next == sum {
+                    break;
+                }
+                sum = next;
+            }
+            return k;
+        }
+
+        let ln_lambda = lambda.ln();
+        let b = 0.931 + 2.53 * lambda.sqrt();
+        let a = -0.059 + 0.02483 * b;
+        let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+        let v_r = 0.9277 - 3.6224 / (b - 2.0);
+
+        loop {
+            let u = source.read::<f64>() - 0.5;
+            let v = source.read::<f64>();
+            let us = 0.5 - u.abs();
+            let k = ((2.0 * a / us + b) * u + lambda + 0.43).floor();
+            if us >= 0.07 && v <= v_r {
+                return k as usize;
+            }
+            if k < 0.0 || (us < 0.013 && v > us) {
+                continue;
+            }
+            if (v * inv_alpha / (a / (us * us) + b)).ln()
+                <= -lambda + k * ln_lambda - (k + 1.0).ln_gamma().0
+            {
+                return k as usize;
+            }
+        }
+    }
+}
+
+impl distribution::Skewness for Poisson {
+    #[inline]
+    fn skewness(&self) -> f64 {
+        self.lambda.sqrt().recip()
+    }
+}
+
+impl distribution::Variance for Poisson {
+    #[inline]
+    fn variance(&self) -> f64 {
+        self.lambda
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($lambda:expr) => (Poisson::new($lambda));
+    );
+
+    #[test]
+    fn distribution() {
+        let d = new!(5.0);
+        let p = vec![
+            0.000000000000000e+00,
+            6.737946999085467e-03,
+            1.246520194830811e-01,
+            4.404932850652124e-01,
+            7.621834629729387e-01,
+            9.319063652781514e-01,
+            9.863047314016170e-01,
+            9.979811483725630e-01,
+            9.997737463238232e-01,
+            9.999801309563696e-01,
+            9.999985983021079e-01,
+        ];
+
+        let x = (-1..10)
+            .map(|i| d.distribution(2.0 * i as f64))
+            .collect::<Vec<_>>();
+        assert::close(&x, &p, 1e-14);
+
+        let x = (-1..10)
+            .map(|i| d.distribution(2.0 * i as f64 + 0.5))
+            .collect::<Vec<_>>();
+        assert::close(&x, &p, 1e-14);
+    }
+
+    #[test]
+    fn entropy() {
+        assert::close(new!(5.0).entropy(), 2.204395243428368, 1e-14);
+        assert::close(new!(0.25).entropy(), 0.6175119998423526, 1e-14);
+    }
+
+    #[test]
+    fn fit() {
+        use distribution::FitError;
+
+        let data = vec![3, 5, 4, 6, 2, 5, 4, 3, 5, 4];
+        let estimate = Poisson::fit(&data).unwrap();
+        assert::close(estimate.distribution.lambda(), 4.1, 1e-15);
+        assert::close(estimate.ln_likelihood, -1.7902088128295386e+01, 1e-13);
+
+        assert_eq!(Poisson::fit(&[]).unwrap_err(), FitError::Insufficient);
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![3, 5, 4, 6, 2, 5, 4, 3, 5, 4];
+        let estimate = Poisson::fit_moments(&data).unwrap();
+        assert::close(estimate.distribution.lambda(), 4.1, 1e-15);
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!(5.0);
+        assert_eq!(d.inverse(0.0), 0);
+        assert_eq!(d.inverse(0.006), 0);
+        assert_eq!(d.inverse(0.5), 5);
+        assert_eq!(d.inverse(0.99), 11);
+
+        for &lambda in &[0.5, 5.0, 42.0, 1000.0, 1e6] {
+            let d = new!(lambda);
+            for &p in &[0.001, 0.1, 0.25, 0.5, 0.75, 0.9, 0.999] {
+                let x = d.inverse(p);
+                assert!(d.distribution(x as f64) >= p);
+                assert!(x == 0 || d.distribution(x as f64 - 1.0) < p);
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_survival() {
+        for &lambda in &[0.5, 5.0, 1000.0] {
+            let d = new!(lambda);
+            for &q in &[1e-300, 1e-20, 0.001, 0.5, 0.9, 1.0] {
+                let x = d.inverse_survival(q);
+                assert!(d.survival(x as f64) <= q);
+                assert!(x == 0 || d.survival(x as f64 - 1.0) > q);
+            }
+        }
+    }
+
+    #[test]
+    fn kurtosis() {
+        assert_eq!(new!(4.0).kurtosis(), 0.25);
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(5.0);
+        for x in -1..25 {
+            let x = x as f64;
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-13);
+        }
+        assert::close(d.ln_distribution(20.0), -8.1092507886879051e-08, 1e-20);
+    }
+
+    #[test]
+    fn ln_mass() {
+        let d = new!(5.0);
+        for x in 0..25 {
+            assert::close(d.ln_mass(x), d.mass(x).ln(), 1e-13);
+        }
+        assert::close(d.ln_mass(1000) / -4.3076902660540630e+03, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(5.0);
+        for x in -1..15 {
+            let x = x as f64;
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-12);
+        }
+        assert::close(d.ln_survival(100.0) / -2.1075103082558560e+02, 1.0, 1e-14);
+    }
+
+    #[test]
+    fn mass() {
+        let d = new!(5.0);
+        let p = vec![
+            6.737946999085467e-03,
+            8.422433748856833e-02,
+            1.754673697678507e-01,
+            1.462228081398756e-01,
+            6.527803934815875e-02,
+            1.813278870782187e-02,
+            3.434240285572324e-03,
+            4.717363029632314e-04,
+            4.913919822533660e-05,
+            4.014640377886977e-06,
+        ];
+
+        assert::close(
+            &(0..10).map(|i| d.mass(2 * i)).collect::<Vec<_>>(),
+            &p,
+            1e-14,
+        );
+
+        let d = new!(1000.0);
+        assert::close(d.mass(1000), 1.261461134872150e-02, 1e-15);
+        assert::close(d.mass(950), 3.629619066304596e-03, 1e-15);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!(4.5).mean(), 4.5);
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(new!(5.0).median(), 5.0);
+        assert_eq!(new!(0.5).median(), 0.0);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(new!(4.5).modes(), vec![4]);
+        assert_eq!(new!(5.0).modes(), vec![4, 5]);
+    }
+
+    #[test]
+    fn sample() {
+        let mut source = source::default();
+        for &lambda in &[0.5, 5.0, 42.0, 1000.0] {
+            let n = 10000;
+            let sum = Independent(&new!(lambda), &mut source)
+                .take(n)
+                .fold(0, |a, b| a + b);
+            let mean = sum as f64 / n as f64;
+            assert!((mean - lambda).abs() < 5.0 * (lambda / n as f64).sqrt());
+        }
+    }
+
+    #[test]
+    fn skewness() {
+        assert_eq!(new!(4.0).skewness(), 0.5);
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!(5.0);
+        for &x in &[-1.0, 0.0, 1.0, 4.0, 8.5, 16.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-14);
+        }
+        assert::close(d.survival(100.0) / 2.9647648771642058e-92, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Poisson::try_new(5.0).is_ok());
+        assert_eq!(
+            Poisson::try_new(0.0).unwrap_err(),
+            Error::NotPositive("lambda")
+        );
+    }
+
+    #[test]
+    fn variance() {
+        assert_eq!(new!(4.5).variance(), 4.5);
+    }
+}
@@ -0,0 +1,82 @@
+use rand::distributions;
+use rand::Rng;
+
+use distribution::{
+    self, AnyDistribution, Bernoulli, Beta, Binomial, Categorical, ChiSquared, Exponential,
+    FisherSnedecor, Gamma, Gaussian, Laplace, Logistic, Lognormal, Pert, Poisson, Sample,
+    StudentsT, Triangular, Uniform, WeightedCategorical,
+};
+use source::Rand;
+
+macro_rules! implement(
+    ($($kind:ident,)*) => ($(
+        impl distributions::Distribution<<$kind as distribution::Distribution>::Value> for $kind {
+            #[inline]
+            fn sample<R>(&self, rng: &mut R) -> <$kind as distribution::Distribution>::Value
+            where
+                R: Rng + ?Sized,
+            {
+                Sample::sample(self, &mut Rand(rng))
+            }
+        }
+    )*);
+);
+
+implement! {
+    AnyDistribution,
+    Bernoulli,
+    Beta,
+    Binomial,
+    Categorical,
+    ChiSquared,
+    Exponential,
+    FisherSnedecor,
+    Gamma,
+    Gaussian,
+    Laplace,
+    Logistic,
+    Lognormal,
+    Pert,
+    Poisson,
+    StudentsT,
+    Triangular,
+    Uniform,
+    WeightedCategorical,
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::distributions::Distribution;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use prelude::*;
+
+    #[test]
+    fn sample() {
+        let mut rng = StdRng::from_seed([42; 32]);
+        let d = Uniform::new(2.0, 3.0);
+        let n = 10000;
+        let mean = d.sample_iter(&mut rng).take(n).sum::<f64>() / n as f64;
+        assert!((mean - 2.5).abs() < 0.01);
+
+        let d = Categorical::new(&[0.0, 1.0]);
+        assert_eq!(Distribution::sample(&d, &mut rng), 1);
+
+        let d = AnyDistribution::from(d);
+        assert_eq!(Distribution::sample(&d, &mut rng), 1.0);
+    }
+
+    #[test]
+    fn source() {
+        let mut source = source::Rand(StdRng::from_seed([42; 32]));
+        let mut rng = StdRng::from_seed([42; 32]);
+        let d = Gaussian::new(0.0, 1.0);
+        for _ in 0..10 {
+            assert_eq!(
+                Sample::sample(&d, &mut source),
+                Distribution::sample(&d, &mut rng)
+            );
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use serde::de;
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use distribution::{
+    Bernoulli, Beta, Binomial, Categorical, ChiSquared, Dirichlet, Exponential, FisherSnedecor,
+    Gamma, Gaussian, Laplace, Logistic, Lognormal, Multinomial, MultivariateGaussian, Pert,
+    Poisson, StudentsT, Triangular, Uniform, WeightedCategorical,
+};
+
+// Only the defining parameters are serialized, and deserialization goes
+// through the validating constructor, so that invalid parameters are reported
+// as errors.
+macro_rules! implement(
+    ($($kind:ident { $($field:ident: $type:ty),* } => $constructor:expr,)*) => ($(
+        impl Serialize for $kind {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                let count = [$(stringify!($field)),*].len();
+                let mut state = serializer.serialize_struct(stringify!($kind), count)?;
+                $(state.serialize_field(stringify!($field), &self.$field())?;)*
+                state.end()
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $kind {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                #[derive(Deserialize)]
+                #[serde(deny_unknown_fields)]
+                struct Parameters {
+                    $($field: $type),*
+                }
+                let Parameters { $($field),* } = Parameters::deserialize(deserializer)?;
+                $constructor.map_err(de::Error::custom)
+            }
+        }
+    )*);
+);
+
+implement! {
+    Bernoulli { p: f64 } => Bernoulli::try_new(p),
+    Beta { alpha: f64, beta: f64, a: f64, b: f64 } => Beta::try_new(alpha, beta, a, b),
+    Binomial { n: usize, p: f64 } => Binomial::try_new(n, p),
+    ChiSquared { k: f64 } => ChiSquared::try_new(k),
+    Dirichlet { alpha: Vec<f64> } => Dirichlet::try_new(&alpha),
+    Exponential { lambda: f64 } => Exponential::try_new(lambda),
+    FisherSnedecor { d1: f64, d2: f64 } => FisherSnedecor::try_new(d1, d2),
+    Gamma { k: f64, theta: f64 } => Gamma::try_new(k, theta),
+    Gaussian { mu: f64, sigma: f64 } => Gaussian::try_new(mu, sigma),
+    Laplace { mu: f64, b: f64 } => Laplace::try_new(mu, b),
+    Logistic { mu: f64, s: f64 } => Logistic::try_new(mu, s),
+    Lognormal { mu: f64, sigma: f64 } => Lognormal::try_new(mu, sigma),
+    Multinomial { n: usize, p: Vec<f64> } => Multinomial::try_new(n, &p),
+    MultivariateGaussian { mu: Vec<f64>, sigma: Vec<f64> } => {
+        MultivariateGaussian::try_new(&mu, &sigma)
+    },
+    Pert { a: f64, b: f64, c: f64 } => Pert::try_new(a, b, c),
+    Poisson { lambda: f64 } => Poisson::try_new(lambda),
+    StudentsT { nu: f64, mu: f64, sigma: f64 } => StudentsT::try_new(nu, mu, sigma),
+    Triangular { a: f64, b: f64, c: f64 } => Triangular::try_new(a, b, c),
+    Uniform { a: f64, b: f64 } => Uniform::try_new(a, b),
+    WeightedCategorical { weights: Vec<f64> } => WeightedCategorical::try_new(&weights),
+}
+
+// The alias table is not serialized, but its presence is flagged, so that the
+// table is rebuilt on deserialization. The flag is omitted when unset.
+impl Serialize for Categorical {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let alias = self.has_alias();
+        let mut state = serializer.serialize_struct("Categorical", 1 + alias as usize)?;
+        state.serialize_field("p", &self.p())?;
+        if alias {
+            state.serialize_field("alias", &alias)?;
+        } else {
+            state.skip_field("alias")?;
+        }
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Categorical {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Parameters {
+            p: Vec<f64>,
+            #[serde(default)]
+            alias: bool,
+        }
+        let Parameters { p, alias } = Parameters::deserialize(deserializer)?;
+        if alias {
+            Categorical::try_with_alias(&p)
+        } else {
+            Categorical::try_new(&p)
+        }
+        .map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use serde_json;
+
+    #[test]
+    fn any_distribution() {
+        let d = AnyDistribution::from(Gaussian::new(1.0, 2.0));
+        let json = serde_json::to_string(&d).unwrap();
+        assert_eq!(json, r#"{"Gaussian":{"mu":1.0,"sigma":2.0}}"#);
+        let d: AnyDistribution = serde_json::from_str(&json).unwrap();
+        assert_eq!(d.mean(), 1.0);
+    }
+
+    #[test]
+    fn deserialize() {
+        let d: Beta = serde_json::from_str(r#"{"alpha":2.0,"beta":3.0,"a":0.0,"b":1.0}"#).unwrap();
+        assert::close(d.mean(), 0.4, 1e-15);
+
+        let d: Binomial = serde_json::from_str(r#"{"n":16,"p":0.25}"#).unwrap();
+        assert_eq!(d.variance(), 3.0);
+
+        let d: Categorical = serde_json::from_str(r#"{"p":[0.25,0.75]}"#).unwrap();
+        assert_eq!(d.distribution(0.0), 0.25);
+        assert!(!d.has_alias());
+
+        let d: Categorical = serde_json::from_str(r#"{"p":[0.25,0.75],"alias":true}"#).unwrap();
+        assert!(d.has_alias());
+    }
+
+    #[test]
+    fn invalid() {
+        let error = serde_json::from_str::<Gaussian>(r#"{"mu":0.0,"sigma":-1.0}"#).unwrap_err();
+        assert!(error.to_string().starts_with("`sigma` should be positive"));
+        assert!(serde_json::from_str::<Categorical>(r#"{"p":[0.25,0.25]}"#).is_err());
+        assert!(serde_json::from_str::<Gaussian>(r#"{"mu":0.0}"#).is_err());
+        assert!(serde_json::from_str::<Gaussian>(r#"{"mu":0.0,"sigma":1.0,"x":1.0}"#).is_err());
+    }
+
+    #[test]
+    fn serialize() {
+        let json = serde_json::to_string(&Binomial::new(16, 0.25)).unwrap();
+        assert_eq!(json, r#"{"n":16,"p":0.25}"#);
+
+        let json = serde_json::to_string(&Categorical::new(&[0.25, 0.75])).unwrap();
+        assert_eq!(json, r#"{"p":[0.25,0.75]}"#);
+
+        let json = serde_json::to_string(&Categorical::with_alias(&[0.25, 0.75])).unwrap();
+        assert_eq!(json, r#"{"p":[0.25,0.75],"alias":true}"#);
+
+        let json = serde_json::to_string(&Pert::new(1.0, 2.0, 4.0)).unwrap();
+        assert_eq!(json, r#"{"a":1.0,"b":2.0,"c":4.0}"#);
+    }
+}
@@ -0,0 +1,471 @@
+use distribution::{self, error, Error};
+use source::Source;
+
+/// A Student’s t distribution.
+#[derive(Clone, Copy, Debug)]
+pub struct StudentsT {
+    nu: f64,
+    mu: f64,
+    sigma: f64,
+    ln_beta: f64,
+}
+
+impl StudentsT {
+    /// Create a Student’s t distribution with `nu` degrees of freedom,
+    /// location `mu`, and scale `sigma`.
+    ///
+    /// It should hold that `nu > 0` and `sigma > 0`.
+    #[inline]
+    pub fn new(nu: f64, mu: f64, sigma: f64) -> Self {
+        use special::Beta;
+        should!(nu > 0.0 && sigma > 0.0);
+        StudentsT {
+            nu: nu,
+            mu: mu,
+            sigma: sigma,
+            ln_beta: (0.5 * nu).ln_beta(0.5),
+        }
+    }
+
+    /// Create a Student’s t distribution with `nu` degrees of freedom,
+    /// location `mu`, and scale `sigma` or fail if the parameters are invalid.
+    #[inline]
+    pub fn try_new(nu: f64, mu: f64, sigma: f64) -> Result<Self, Error> {
+        error::positive(nu, "nu")?;
+        error::finite(mu, "mu")?;
+        error::positive(sigma, "sigma")?;
+        Ok(StudentsT::new(nu, mu, sigma))
+    }
+
+    /// Return the degrees of freedom.
+    #[inline(always)]
+    pub fn nu(&self) -> f64 {
+        self.nu
+    }
+
+    /// Return the location parameter.
+    #[inline(always)]
+    pub fn mu(&self) -> f64 {
+        self.mu
+    }
+
+    /// Return the scale parameter.
+    #[inline(always)]
+    pub fn sigma(&self) -> f64 {
+        self.sigma
+    }
+}
+
+impl distribution::Continuous for StudentsT {
+    fn density(&self, x: f64) -> f64 {
+        let t = (x - self.mu) / self.sigma;
+        (-0.5 * (self.nu + 1.0) * (t * t / self.nu).ln_1p() - self.ln_beta).exp()
+            / (self.sigma * self.nu.sqrt())
+    }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        let t = (x - self.mu) / self.sigma;
+        -0.5 * (self.nu + 1.0) * (t * t / self.nu).ln_1p()
+            - self.ln_beta
+            - (self.sigma * self.nu.sqrt()).ln()
+    }
+}
+
+impl distribution::Distribution for StudentsT {
+    type Value = f64;
+
+    /// Compute the cumulative distribution function.
+    ///
+    /// The implementation is based on the incomplete beta function.
+    #[inline]
+    fn distribution(&self, x: f64) -> f64 {
+        distribution((x - self.mu) / self.sigma, self.nu, self.ln_beta)
+    }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        distribution((x - self.mu) / self.sigma, self.nu, self.ln_beta).ln()
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        distribution((self.mu - x) / self.sigma, self.nu, self.ln_beta)
+    }
+}
+
+impl distribution::Entropy for StudentsT {
+    fn entropy(&self) -> f64 {
+        use special::Gamma;
+        let nu = self.nu;
+        0.5 * (nu + 1.0) * ((0.5 * (nu + 1.0)).digamma() - (0.5 * nu).digamma())
+            + 0.5 * nu.ln()
+            + self.ln_beta
+            + self.sigma.ln()
+    }
+}
+
+impl distribution::Inverse for StudentsT {
+    /// Compute the inverse of the cumulative distribution function.
+    ///
+    /// The implementation is based on the inverse of the incomplete beta
+    /// function.
+    #[inline]
+    fn inverse(&self, p: f64) -> f64 {
+        self.mu + self.sigma * inverse(p, self.nu, self.ln_beta)
+    }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        self.mu - self.sigma * inverse(q, self.nu, self.ln_beta)
+    }
+}
+
+impl distribution::Kurtosis for StudentsT {
+    /// Compute the excess kurtosis.
+    ///
+    /// The kurtosis is infinite if `nu <= 4` and undefined if `nu <= 2`.
+    fn kurtosis(&self) -> f64 {
+        use std::f64::{INFINITY, NAN};
+        if self.nu > 4.0 {
+            6.0 / (self.nu - 4.0)
+        } else if self.nu > 2.0 {
+            INFINITY
+        } else {
+            NAN
+        }
+    }
+}
+
+impl distribution::Mean for StudentsT {
+    /// Compute the expected value.
+    ///
+    /// The expected value is undefined if `nu <= 1`.
+    #[inline]
+    fn mean(&self) -> f64 {
+        if self.nu > 1.0 {
+            self.mu
+        } else {
+            ::std::f64::NAN
+        }
+    }
+}
+
+impl distribution::Median for StudentsT {
+    #[inline]
+    fn median(&self) -> f64 {
+        self.mu
+    }
+}
+
+impl distribution::Modes for StudentsT {
+    #[inline]
+    fn modes(&self) -> Vec<f64> {
+        vec![self.mu]
+    }
+}
+
+impl distribution::Sample for StudentsT {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        use distribution::{gamma, gaussian};
+        let z = gaussian::sample(source);
+        let v = 2.0 * gamma::sample(0.5 * self.nu, source);
+        self.mu + self.sigma * z * (self.nu / v).sqrt()
+    }
+}
+
+impl distribution::Skewness for StudentsT {
+    /// Compute the skewness.
+    ///
+    /// The skewness is undefined if `nu <= 3`.
+    #[inline]
+    fn skewness(&self) -> f64 {
+        if self.nu > 3.0 {
+            0.0
+        } else {
+            ::std::f64::NAN
+        }
+    }
+}
+
+impl distribution::Variance for StudentsT {
+    /// Compute the variance.
+    ///
+    /// The variance is infinite if `nu <= 2` and undefined if `nu <= 1`.
+    fn variance(&self) -> f64 {
+        use std::f64::{INFINITY, NAN};
+        if self.nu > 2.0 {
+            self.sigma * self.sigma * self.nu / (self.nu - 2.0)
+        } else if self.nu > 1.0 {
+            INFINITY
+        } else {
+            NAN
+        }
+    }
+}
+
+// Compute the distribution function of the standard Student’s t distribution.
+// The tail that is further from the median is evaluated directly in order to
+// retain accuracy there.
+fn distribution(t: f64, nu: f64, ln_beta: f64) -> f64 {
+    use special::Beta;
+    let t2 = t * t;
+    if t2 < nu {
+        let half = 0.5 * (t2 / (nu + t2)).inc_beta(0.5, 0.5 * nu, ln_beta);
+        if t < 0.0 {
+            0.5 - half
+        } else {
+            0.5 + half
+        }
+    } else {
+        let tail = 0.5 * (nu / (nu + t2)).inc_beta(0.5 * nu, 0.5, ln_beta);
+        if t < 0.0 {
+            tail
+        } else {
+            1.0 - tail
+        }
+    }
+}
+
+// Compute the inverse of the distribution function of the standard Student’s t
+// distribution.
+fn inverse(p: f64, nu: f64, ln_beta: f64) -> f64 {
+    use special::Beta;
+    use std::f64::{INFINITY, NEG_INFINITY};
+
+    should!(0.0 <= p && p <= 1.0);
+
+    if p <= 0.0 {
+        return NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return INFINITY;
+    }
+    if p == 0.5 {
+        return 0.0;
+    }
+
+    let q = 2.0 * if p < 0.5 { p } else { 1.0 - p };
+    let t = if q < 0.5 {
+        let x = q.inv_inc_beta(0.5 * nu, 0.5, ln_beta);
+        (nu * (1.0 - x) / x).sqrt()
+    } else {
+        let x = (1.0 - q).inv_inc_beta(0.5, 0.5 * nu, ln_beta);
+        (nu * x / (1.0 - x)).sqrt()
+    };
+    if p < 0.5 {
+        -t
+    } else {
+        t
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($nu:expr, $mu:expr, $sigma:expr) => (StudentsT::new($nu, $mu, $sigma));
+    );
+
+    #[test]
+    fn density() {
+        let d = new!(3.5, 1.0, 2.0);
+        let x = vec![-10.0, -4.0, -2.0, -1.0, 0.0, 1.0, 2.0, 3.0, 5.0, 8.0, 20.0];
+        let p = vec![
+            1.1344469316828098e-03,
+            1.8541283658881920e-02,
+            6.0834162412271152e-02,
+            1.0560197180890556e-01,
+            1.5915849765738491e-01,
+            1.8588617186604767e-01,
+            1.5915849765738491e-01,
+            1.0560197180890556e-01,
+            3.3458952534315418e-02,
+            6.3025881368947590e-03,
+            1.1388455038676917e-04,
+        ];
+
+        assert::close(
+            &x.iter().map(|&x| d.density(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn distribution() {
+        let d = new!(3.5, 1.0, 2.0);
+        let x = vec![-10.0, -4.0, -2.0, -1.0, 0.0, 1.0, 2.0, 3.0, 5.0, 8.0, 20.0];
+        let p = vec![
+            3.8979003187873001e-03,
+            3.7847321904745039e-02,
+            1.0890909350767261e-01,
+            1.9066862678172278e-01,
+            3.2342521966127552e-01,
+            5.0000000000000000e-01,
+            6.7657478033872454e-01,
+            8.0933137321827719e-01,
+            9.3693073871204324e-01,
+            9.8455138287720323e-01,
+            9.9936225331765782e-01,
+        ];
+
+        assert::close(
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            &p,
+            1e-14,
+        );
+
+        assert::close(new!(1.0, 0.0, 1.0).distribution(1.0), 0.75, 1e-15);
+    }
+
+    #[test]
+    fn entropy() {
+        assert::close(new!(3.5, 1.0, 2.0).entropy(), 2.4140373012424123, 1e-14);
+        assert::close(new!(1.0, 0.0, 1.0).entropy(), 2.5310242469692907, 1e-14);
+    }
+
+    #[test]
+    fn inverse() {
+        use std::f64::{INFINITY, NEG_INFINITY};
+
+        let d = new!(3.5, 1.0, 2.0);
+        let p = vec![
+            0.0, 0.001, 0.05, 0.1, 0.25, 0.5, 0.75, 0.9, 0.95, 0.999, 1.0,
+        ];
+        let x = vec![
+            NEG_INFINITY,
+            -1.5631135209032236e+01,
+            -3.4448669872992030e+00,
+            -2.1531532102728099e+00,
+            -5.0187405878815727e-01,
+            1.0000000000000000e+00,
+            2.5018740587881574e+00,
+            4.1531532102728104e+00,
+            5.4448669872992035e+00,
+            1.7631135209032234e+01,
+            INFINITY,
+        ];
+
+        assert::close(
+            &p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
+            &x,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn inverse_survival() {
+        let d = new!(3.5, 1.0, 2.0);
+        for &q in &[0.001, 0.1, 0.5, 0.9, 0.999] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-10);
+        }
+        assert::close(
+            d.inverse_survival(1e-10) / 1.6980866912669527e+03,
+            1.0,
+            1e-10,
+        );
+    }
+
+    #[test]
+    fn kurtosis() {
+        assert_eq!(new!(7.0, 0.0, 1.0).kurtosis(), 2.0);
+        assert!(new!(3.0, 0.0, 1.0).kurtosis().is_infinite());
+        assert!(new!(2.0, 0.0, 1.0).kurtosis().is_nan());
+    }
+
+    #[test]
+    fn ln_density() {
+        let d = new!(3.5, 1.0, 2.0);
+        for &x in &[-10.0, -1.0, 0.0, 1.0, 2.5, 20.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(3.5, 1.0, 2.0);
+        for &x in &[-10.0, -1.0, 0.0, 1.0, 2.5, 20.0] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+        assert::close(d.ln_distribution(-1e3), -2.1178166481574467e+01, 1e-12);
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(3.5, 1.0, 2.0);
+        for &x in &[-10.0, -1.0, 0.0, 1.0, 2.5, 20.0] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-13);
+        }
+        assert::close(d.ln_survival(1e3), -2.1171166559422043e+01, 1e-12);
+    }
+
+    #[test]
+    fn mean() {
+        assert_eq!(new!(3.5, 1.0, 2.0).mean(), 1.0);
+        assert!(new!(1.0, 1.0, 2.0).mean().is_nan());
+    }
+
+    #[test]
+    fn median() {
+        assert_eq!(new!(3.5, 1.0, 2.0).median(), 1.0);
+    }
+
+    #[test]
+    fn modes() {
+        assert_eq!(new!(3.5, 1.0, 2.0).modes(), vec![1.0]);
+    }
+
+    #[test]
+    fn sample() {
+        let d = new!(10.0, 1.0, 2.0);
+        let n = 10000;
+        let sum = Independent(&d, &mut source::default())
+            .take(n)
+            .fold(0.0, |a, b| a + b);
+        assert!((sum / n as f64 - 1.0).abs() < 5.0 * (d.variance() / n as f64).sqrt());
+    }
+
+    #[test]
+    fn skewness() {
+        assert_eq!(new!(3.5, 1.0, 2.0).skewness(), 0.0);
+        assert!(new!(3.0, 1.0, 2.0).skewness().is_nan());
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!(3.5, 1.0, 2.0);
+        for &x in &[-5.0, -1.0, 0.0, 1.0, 2.5, 10.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-14);
+        }
+        assert::close(d.survival(1e3) / 6.3896811605588929e-10, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(StudentsT::try_new(3.5, 1.0, 2.0).is_ok());
+        assert_eq!(
+            StudentsT::try_new(0.0, 1.0, 2.0).unwrap_err(),
+            Error::NotPositive("nu")
+        );
+    }
+
+    #[test]
+    fn variance() {
+        assert_eq!(new!(4.0, 1.0, 2.0).variance(), 8.0);
+        assert!(new!(2.0, 1.0, 2.0).variance().is_infinite());
+        assert!(new!(1.0, 1.0, 2.0).variance().is_nan());
+    }
+
+    #[test]
+    fn deviation() {
+        assert_eq!(new!(4.0, 1.0, 1.0).deviation(), 2f64.sqrt());
+    }
+}
@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, Error};
 use source::Source;
 
 /// A triangular distribution.
@@ -19,6 +19,16 @@ impl Triangular {
         Triangular { a: a, b: b, c: c }
     }
 
+    /// Create a triangular distribution with mode `c` on interval `[a, b]` or
+    /// fail if the parameters are invalid.
+    #[inline]
+    pub fn try_new(a: f64, b: f64, c: f64) -> Result<Self, Error> {
+        error::less(a, b, ("a", "b"))?;
+        error::less_or_equal(a, c, ("a", "c"))?;
+        error::less_or_equal(c, b, ("c", "b"))?;
+        Ok(Triangular::new(a, b, c))
+    }
+
     /// Return the left endpoint of the support.
     #[inline(always)]
     pub fn a(&self) -> f64 {
@@ -54,6 +64,22 @@ impl distribution::Continuous for Triangular {
             factor
         }
     }
+
+    fn ln_density(&self, x: f64) -> f64 {
+        nonnan!(x);
+        let &Triangular { a, b, c } = self;
+        if x < a || b < x {
+            ::std::f64::NEG_INFINITY
+        } else {
+            let mut value = (2.0 / (b - a)).ln();
+            if x < c {
+                value += ((x - a) / (c - a)).ln();
+            } else if x > c {
+                value += ((b - x) / (b - c)).ln();
+            }
+            value
+        }
+    }
 }
 
 impl distribution::Distribution for Triangular {
@@ -75,6 +101,57 @@ impl distribution::Distribution for Triangular {
             }
         }
     }
+
+    fn ln_distribution(&self, x: f64) -> f64 {
+        nonnan!(x);
+        let &Triangular { a, b, c } = self;
+        if x <= a {
+            ::std::f64::NEG_INFINITY
+        } else if b <= x {
+            0.0
+        } else {
+            let diff = b - a;
+            if x <= c {
+                2.0 * (x - a).ln() - diff.ln() - (c - a).ln()
+            } else {
+                (-(b - x).powi(2) / diff / (b - c)).ln_1p()
+            }
+        }
+    }
+
+    fn ln_survival(&self, x: f64) -> f64 {
+        nonnan!(x);
+        let &Triangular { a, b, c } = self;
+        if x <= a {
+            0.0
+        } else if b <= x {
+            ::std::f64::NEG_INFINITY
+        } else {
+            let diff = b - a;
+            if x < c {
+                (-(x - a).powi(2) / diff / (c - a)).ln_1p()
+            } else {
+                2.0 * (b - x).ln() - diff.ln() - (b - c).ln()
+            }
+        }
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        nonnan!(x);
+        let &Triangular { a, b, c } = self;
+        if x <= a {
+            1.0
+        } else if b <= x {
+            0.0
+        } else {
+            let diff = b - a;
+            if x < c {
+                1.0 - (x - a).powi(2) / diff / (c - a)
+            } else {
+                (b - x).powi(2) / diff / (b - c)
+            }
+        }
+    }
 }
 
 impl distribution::Entropy for Triangular {
@@ -104,6 +181,26 @@ impl distribution::Inverse for Triangular {
             }
         }
     }
+
+    fn inverse_survival(&self, q: f64) -> f64 {
+        should!(0.0 <= q && q <= 1.0);
+        nonnan!(q);
+        let &Triangular { a, b, c } = self;
+        if q == 0.0 {
+            b
+        } else if q == 1.0 {
+            a
+        } else {
+            let q0 = (b - c) / (b - a);
+            if q < q0 {
+                b - ((b - a) * (b - c) * q).sqrt()
+            } else if q > q0 {
+                ((b - a) * (c - a) * (1.0 - q)).sqrt() + a
+            } else {
+                c
+            }
+        }
+    }
 }
 
 impl distribution::Kurtosis for Triangular {
@@ -225,11 +322,43 @@ mod tests {
         );
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(1.0, 5.0, 3.0);
+        for &q in &[0.0, 0.001, 0.1, 0.5, 0.9, 0.999, 1.0] {
+            assert::close(d.inverse_survival(q), d.inverse(1.0 - q), 1e-12);
+        }
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(1.0, 5.0, 3.0).kurtosis(), -(3.0 / 5.0));
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(1.0, 5.0, 3.0);
+        for &x in &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(1.0, 5.0, 3.0);
+        for &x in &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(1.0, 5.0, 3.0);
+        for &x in &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-14);
+        }
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(1.0, 5.0, 3.0).mean(), 3.0);
@@ -250,6 +379,25 @@ mod tests {
         assert_eq!(new!(1.0, 5.0, 3.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(1.0, 5.0, 3.0);
+        for &x in &[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-15);
+        }
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Triangular::try_new(1.0, 5.0, 3.0).is_ok());
+        assert_eq!(
+            Triangular::try_new(1.0, 5.0, 6.0).unwrap_err(),
+            Error::NotLessOrEqual("c", "b")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(1.0, 5.0, 3.0).variance(), (12.0 / 18.0));
@@ -1,4 +1,4 @@
-use distribution;
+use distribution::{self, error, fit, Error, Estimate, FitError};
 use source::Source;
 
 /// A continuous uniform distribution.
@@ -18,6 +18,14 @@ impl Uniform {
         Uniform { a: a, b: b }
     }
 
+    /// Create a uniform distribution on interval `[a, b]` or fail if the
+    /// parameters are invalid.
+    #[inline]
+    pub fn try_new(a: f64, b: f64) -> Result<Self, Error> {
+        error::less(a, b, ("a", "b"))?;
+        Ok(Uniform::new(a, b))
+    }
+
     /// Return the left endpoint of the support.
     #[inline(always)]
     pub fn a(&self) -> f64 {
@@ -47,6 +55,15 @@ impl distribution::Continuous for Uniform {
             1.0 / (self.b - self.a)
         }
     }
+
+    #[inline]
+    fn ln_density(&self, x: f64) -> f64 {
+        if x < self.a || x > self.b {
+            ::std::f64::NEG_INFINITY
+        } else {
+            -(self.b - self.a).ln()
+        }
+    }
 }
 
 impl distribution::Distribution for Uniform {
@@ -62,6 +79,39 @@ impl distribution::Distribution for Uniform {
             (x - self.a) / (self.b - self.a)
         }
     }
+
+    #[inline]
+    fn ln_distribution(&self, x: f64) -> f64 {
+        if x <= self.a {
+            ::std::f64::NEG_INFINITY
+        } else if x >= self.b {
+            0.0
+        } else {
+            ((x - self.a) / (self.b - self.a)).ln()
+        }
+    }
+
+    #[inline]
+    fn ln_survival(&self, x: f64) -> f64 {
+        if x <= self.a {
+            0.0
+        } else if x >= self.b {
+            ::std::f64::NEG_INFINITY
+        } else {
+            ((self.b - x) / (self.b - self.a)).ln()
+        }
+    }
+
+    #[inline]
+    fn survival(&self, x: f64) -> f64 {
+        if x <= self.a {
+            1.0
+        } else if x >= self.b {
+            0.0
+        } else {
+            (self.b - x) / (self.b - self.a)
+        }
+    }
 }
 
 impl distribution::Entropy for Uniform {
@@ -71,12 +121,41 @@ impl distribution::Entropy for Uniform {
     }
 }
 
+impl distribution::Fit for Uniform {
+    fn fit(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        use std::f64::{INFINITY, NEG_INFINITY};
+        fit::check(data, 2)?;
+        let a = data.iter().fold(INFINITY, |a, &x| a.min(x));
+        let b = data.iter().fold(NEG_INFINITY, |b, &x| b.max(x));
+        Ok(fit::continuous(Uniform::try_new(a, b)?, data, 0, true))
+    }
+
+    /// Estimate the parameters by the method of moments.
+    fn fit_moments(data: &[f64]) -> Result<Estimate<Self>, FitError> {
+        fit::check(data, 2)?;
+        let mean = fit::mean(data.iter().cloned());
+        let delta = (3.0 * fit::variance(data.iter().cloned(), mean)).sqrt();
+        Ok(fit::continuous(
+            Uniform::try_new(mean - delta, mean + delta)?,
+            data,
+            0,
+            true,
+        ))
+    }
+}
+
 impl distribution::Inverse for Uniform {
     #[inline]
     fn inverse(&self, p: f64) -> f64 {
         should!(0.0 <= p && p <= 1.0);
         self.a + (self.b - self.a) * p
     }
+
+    #[inline]
+    fn inverse_survival(&self, q: f64) -> f64 {
+        should!(0.0 <= q && q <= 1.0);
+        self.b - (self.b - self.a) * q
+    }
 }
 
 impl distribution::Kurtosis for Uniform {
@@ -160,6 +239,24 @@ mod tests {
         assert_eq!(new!(0.0, E).entropy(), 1.0);
     }
 
+    #[test]
+    fn fit() {
+        let data = vec![-1.3, -0.4, 0.2, 0.5, 1.1, 1.7, 2.6, 4.0];
+        let estimate = Uniform::fit(&data).unwrap();
+        assert_eq!(estimate.distribution.a(), -1.3);
+        assert_eq!(estimate.distribution.b(), 4.0);
+        assert::close(estimate.ln_likelihood, -1.3341654564464609e+01, 1e-13);
+    }
+
+    #[test]
+    fn fit_moments() {
+        let data = vec![-1.3, -0.4, 0.2, 0.5, 1.1, 1.7, 2.6, 4.0];
+        let estimate = Uniform::fit_moments(&data).unwrap();
+        assert::close(estimate.distribution.a(), -1.7009089406957840e+00, 1e-15);
+        assert::close(estimate.distribution.b(), 3.8009089406957841e+00, 1e-15);
+        assert_eq!(estimate.ln_likelihood, ::std::f64::NEG_INFINITY);
+    }
+
     #[test]
     fn inverse() {
         let d = new!(-1.0, 1.0);
@@ -169,11 +266,43 @@ mod tests {
         assert_eq!(&p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(), &x);
     }
 
+    #[test]
+    fn inverse_survival() {
+        let d = new!(-1.0, 1.0);
+        for &q in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+            assert_eq!(d.inverse_survival(q), d.inverse(1.0 - q));
+        }
+    }
+
     #[test]
     fn kurtosis() {
         assert_eq!(new!(0.0, 2.0).kurtosis(), -1.2);
     }
 
+    #[test]
+    fn ln_density() {
+        let d = new!(-1.0, 1.0);
+        for &x in &[-1.5, -1.0, 0.0, 0.5, 1.0, 1.5] {
+            assert::close(d.ln_density(x), d.density(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_distribution() {
+        let d = new!(-1.0, 1.0);
+        for &x in &[-1.5, -1.0, 0.0, 0.5, 1.0, 1.5] {
+            assert::close(d.ln_distribution(x), d.distribution(x).ln(), 1e-14);
+        }
+    }
+
+    #[test]
+    fn ln_survival() {
+        let d = new!(-1.0, 1.0);
+        for &x in &[-1.5, -1.0, 0.0, 0.5, 1.0, 1.5] {
+            assert::close(d.ln_survival(x), (1.0 - d.distribution(x)).ln(), 1e-14);
+        }
+    }
+
     #[test]
     fn mean() {
         assert_eq!(new!(0.0, 2.0).mean(), 1.0);
@@ -196,6 +325,25 @@ mod tests {
         assert_eq!(new!(0.0, 2.0).skewness(), 0.0);
     }
 
+    #[test]
+    fn survival() {
+        let d = new!(-1.0, 1.0);
+        for &x in &[-1.5, -1.0, -0.5, 0.0, 0.5, 1.0, 1.5] {
+            assert::close(d.survival(x), 1.0 - d.distribution(x), 1e-15);
+        }
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(Uniform::try_new(-1.0, 1.0).is_ok());
+        assert_eq!(
+            Uniform::try_new(1.0, -1.0).unwrap_err(),
+            Error::NotLess("a", "b")
+        );
+    }
+
     #[test]
     fn variance() {
         assert_eq!(new!(0.0, 12.0).variance(), 12.0);
@@ -0,0 +1,388 @@
+use distribution::{self, error, Error};
+use source::Source;
+
+/// A categorical distribution with updatable weights.
+///
+/// The weights are not required to be normalized. They are kept in a Fenwick
+/// tree, which allows for changing a weight, computing the distribution
+/// function, and drawing samples in logarithmic time. The total weight should
+/// be positive whenever the distribution is queried. In order to keep rounding
+/// errors from accumulating, the tree is rebuilt after as many updates as
+/// there are categories, which keeps the amortized cost of an update
+/// logarithmic.
+#[derive(Clone, Debug)]
+pub struct WeightedCategorical {
+    weights: Vec<f64>,
+    tree: Vec<f64>,
+    total: f64,
+    updates: usize,
+}
+
+impl WeightedCategorical {
+    /// Create a categorical distribution with weights `weights`.
+    ///
+    /// It should hold that `weights[i] >= 0`.
+    pub fn new(weights: &[f64]) -> Self {
+        should!(weights.iter().all(|&w| w >= 0.0 && w.is_finite()));
+        let mut distribution = WeightedCategorical {
+            weights: weights.to_vec(),
+            tree: Vec::new(),
+            total: 0.0,
+            updates: 0,
+        };
+        distribution.rebuild();
+        distribution
+    }
+
+    /// Create a categorical distribution with weights `weights` or fail if the
+    /// parameters are invalid.
+    pub fn try_new(weights: &[f64]) -> Result<Self, Error> {
+        if weights.is_empty() {
+            return Err(Error::Empty("weights"));
+        }
+        for &w in weights {
+            error::non_negative(w, "weights")?;
+        }
+        if weights.iter().fold(0.0, |sum, &w| sum + w) == 0.0 {
+            return Err(Error::Zero("weights"));
+        }
+        Ok(WeightedCategorical::new(weights))
+    }
+
+    /// Return the number of categories.
+    #[inline(always)]
+    pub fn k(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Return the sum of the weights.
+    #[inline(always)]
+    pub fn total(&self) -> f64 {
+        self.total
+    }
+
+    /// Return the weights.
+    #[inline(always)]
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    /// Append a category with weight `w`.
+    ///
+    /// It should hold that `w >= 0`.
+    pub fn push(&mut self, w: f64) {
+        should!(w >= 0.0 && w.is_finite());
+        self.weights.push(w);
+        let n = self.weights.len();
+        let mut value = w;
+        let mut step = 1;
+        while step < lowbit(n) {
+            value += self.tree[n - step - 1];
+            step <<= 1;
+        }
+        self.tree.push(value);
+        self.total += w;
+        self.update();
+    }
+
+    /// Remove category `i` and return its weight.
+    ///
+    /// The categories after `i` are shifted down by one. Removing the last
+    /// category takes constant time; otherwise, the tree is rebuilt in linear
+    /// time.
+    pub fn remove(&mut self, i: usize) -> f64 {
+        let w = self.weights.remove(i);
+        if i == self.weights.len() {
+            self.tree.pop();
+            self.total -= w;
+            self.update();
+        } else {
+            self.rebuild();
+        }
+        w
+    }
+
+    /// Set the weight of category `i` to `w`.
+    ///
+    /// It should hold that `w >= 0`.
+    pub fn set_weight(&mut self, i: usize, w: f64) {
+        should!(w >= 0.0 && w.is_finite());
+        let delta = w - self.weights[i];
+        self.weights[i] = w;
+        self.total += delta;
+        let mut j = i + 1;
+        while j <= self.tree.len() {
+            self.tree[j - 1] += delta;
+            j += lowbit(j);
+        }
+        self.update();
+    }
+
+    // Compute the sum of the first `i` weights.
+    fn prefix(&self, mut i: usize) -> f64 {
+        let mut sum = 0.0;
+        while i > 0 {
+            sum += self.tree[i - 1];
+            i -= lowbit(i);
+        }
+        sum
+    }
+
+    fn rebuild(&mut self) {
+        let n = self.weights.len();
+        self.tree = self.weights.clone();
+        for i in 1..(n + 1) {
+            let j = i + lowbit(i);
+            if j <= n {
+                self.tree[j - 1] += self.tree[i - 1];
+            }
+        }
+        self.total = self.weights.iter().fold(0.0, |sum, &w| sum + w);
+        self.updates = 0;
+    }
+
+    // Count an incremental update and rebuild the tree once the number of
+    // updates reaches the number of categories.
+    fn update(&mut self) {
+        self.updates += 1;
+        if self.updates >= self.weights.len() {
+            self.rebuild();
+        }
+    }
+}
+
+impl distribution::Discrete for WeightedCategorical {
+    #[inline]
+    fn mass(&self, x: usize) -> f64 {
+        should!(x < self.k());
+        self.weights[x] / self.total
+    }
+
+    #[inline]
+    fn ln_mass(&self, x: usize) -> f64 {
+        should!(x < self.k());
+        self.weights[x].ln() - self.total.ln()
+    }
+}
+
+impl distribution::Distribution for WeightedCategorical {
+    type Value = usize;
+
+    fn distribution(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 0.0;
+        }
+        let x = x as usize;
+        if x + 1 >= self.k() {
+            return 1.0;
+        }
+        (self.prefix(x + 1) / self.total).min(1.0)
+    }
+
+    fn survival(&self, x: f64) -> f64 {
+        if x < 0.0 {
+            return 1.0;
+        }
+        let x = x as usize;
+        if x + 1 >= self.k() {
+            return 0.0;
+        }
+        ((self.total - self.prefix(x + 1)) / self.total).max(0.0)
+    }
+}
+
+impl distribution::Inverse for WeightedCategorical {
+    fn inverse(&self, p: f64) -> usize {
+        should!(0.0 <= p && p <= 1.0 && self.total > 0.0);
+        let (n, target) = (self.k(), p * self.total);
+        let mut step = 1;
+        while step <= n / 2 {
+            step <<= 1;
+        }
+        let (mut i, mut sum) = (0, 0.0);
+        while step > 0 {
+            if i + step <= n {
+                let value = sum + self.tree[i + step - 1];
+                if value < target || value <= 0.0 {
+                    i += step;
+                    sum = value;
+                }
+            }
+            step >>= 1;
+        }
+        if i < n && self.weights[i] > 0.0 {
+            i
+        } else {
+            self.weights.iter().rposition(|&w| w > 0.0).unwrap_or(0)
+        }
+    }
+}
+
+impl distribution::Sample for WeightedCategorical {
+    #[inline]
+    fn sample<S>(&self, source: &mut S) -> usize
+    where
+        S: Source,
+    {
+        use distribution::Inverse;
+        self.inverse(source.read::<f64>())
+    }
+}
+
+#[inline(always)]
+fn lowbit(i: usize) -> usize {
+    i & i.wrapping_neg()
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    macro_rules! new(
+        ($weights:expr) => (WeightedCategorical::new(&$weights));
+    );
+
+    #[test]
+    fn distribution() {
+        let d = new!([0.0, 3.0, 1.0, 0.0, 4.0]);
+        let x = vec![-1.0, 0.0, 1.0, 1.5, 2.0, 3.0, 4.0, 5.0];
+        let p = vec![0.0, 0.0, 0.375, 0.375, 0.5, 0.5, 1.0, 1.0];
+        assert::close(
+            &x.iter().map(|&x| d.distribution(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        let d = new!([0.0, 3.0, 1.0, 0.0, 4.0]);
+        let p = vec![0.0, 0.375, 0.3750001, 0.5, 0.5000001, 1.0];
+        assert_eq!(
+            &p.iter().map(|&p| d.inverse(p)).collect::<Vec<_>>(),
+            &vec![1, 1, 2, 2, 4, 4]
+        );
+
+        let weights = (0..100).map(|i| (i % 7) as f64).collect::<Vec<_>>();
+        let d = WeightedCategorical::new(&weights);
+        let c = Categorical::new(&weights.iter().map(|&w| w / d.total()).collect::<Vec<_>>());
+        for i in 0..1000 {
+            let p = i as f64 / 999.0;
+            assert_eq!(d.inverse(p), c.inverse(p));
+        }
+    }
+
+    #[test]
+    fn ln_mass() {
+        let d = new!([0.0, 3.0, 1.0]);
+        assert_eq!(d.ln_mass(0), ::std::f64::NEG_INFINITY);
+        assert::close(d.ln_mass(1), 0.75f64.ln(), 1e-15);
+    }
+
+    #[test]
+    fn mass() {
+        let d = new!([0.0, 3.0, 1.0]);
+        assert_eq!(
+            &(0..3).map(|x| d.mass(x)).collect::<Vec<_>>(),
+            &[0.0, 0.75, 0.25]
+        );
+    }
+
+    #[test]
+    fn push() {
+        let weights = (1..20).map(|i| i as f64).collect::<Vec<_>>();
+        let mut d = new!([]);
+        for &w in &weights {
+            d.push(w);
+        }
+        let e = WeightedCategorical::new(&weights);
+        assert_eq!(d.total(), e.total());
+        for x in 0..19 {
+            assert_eq!(d.distribution(x as f64), e.distribution(x as f64));
+        }
+    }
+
+    #[test]
+    fn rebuild() {
+        let mut d = new!([1.0, 1.0]);
+        for _ in 0..1000 {
+            d.set_weight(0, 1e12);
+            d.set_weight(0, 1e-3);
+        }
+        assert_eq!(d.total(), 1.001);
+        assert::close(d.mass(0) + d.mass(1), 1.0, 1e-15);
+        assert::close(d.distribution(0.0), 1e-3 / 1.001, 1e-15);
+    }
+
+    #[test]
+    fn remove() {
+        let mut d = new!([1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(d.remove(3), 4.0);
+        assert_eq!(d.weights(), &[1.0, 2.0, 3.0]);
+        assert_eq!(d.total(), 6.0);
+        assert_eq!(d.remove(0), 1.0);
+        assert_eq!(d.weights(), &[2.0, 3.0]);
+        assert_eq!(d.total(), 5.0);
+        assert_eq!(d.distribution(0.0), 0.4);
+    }
+
+    #[test]
+    fn sample() {
+        let mut source = source::default();
+        let mut d = new!([1.0, 0.0, 3.0]);
+        let n = 100000;
+        let mut count = [0; 3];
+        for x in Independent(&d, &mut source).take(n) {
+            count[x] += 1;
+        }
+        assert_eq!(count[1], 0);
+        assert!((count[2] as f64 / n as f64 - 0.75).abs() < 0.01);
+
+        d.set_weight(2, 0.0);
+        assert!(Independent(&d, &mut source).take(100).all(|x| x == 0));
+    }
+
+    #[test]
+    fn set_weight() {
+        let mut d = new!([1.0, 2.0, 3.0, 4.0, 5.0]);
+        d.set_weight(1, 7.0);
+        d.set_weight(4, 0.0);
+        let e = new!([1.0, 7.0, 3.0, 4.0, 0.0]);
+        assert_eq!(d.total(), e.total());
+        for x in 0..5 {
+            assert_eq!(d.distribution(x as f64), e.distribution(x as f64));
+        }
+    }
+
+    #[test]
+    fn survival() {
+        let d = new!([0.0, 3.0, 1.0, 0.0, 4.0]);
+        let x = vec![-1.0, 0.0, 1.0, 2.0, 3.0, 4.0];
+        let p = vec![1.0, 1.0, 0.625, 0.5, 0.5, 0.0];
+        assert::close(
+            &x.iter().map(|&x| d.survival(x)).collect::<Vec<_>>(),
+            &p,
+            1e-15,
+        );
+    }
+
+    #[test]
+    fn try_new() {
+        use distribution::Error;
+
+        assert!(WeightedCategorical::try_new(&[1.0, 0.0, 2.0]).is_ok());
+        assert_eq!(
+            WeightedCategorical::try_new(&[]).unwrap_err(),
+            Error::Empty("weights")
+        );
+        assert_eq!(
+            WeightedCategorical::try_new(&[1.0, -1.0]).unwrap_err(),
+            Error::Negative("weights")
+        );
+        assert_eq!(
+            WeightedCategorical::try_new(&[0.0, 0.0]).unwrap_err(),
+            Error::Zero("weights")
+        );
+    }
+}
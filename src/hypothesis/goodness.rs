@@ -0,0 +1,480 @@
+use distribution::{ChiSquared, Discrete, Distribution};
+use hypothesis::Test;
+use special::Gamma;
+
+const EXACT: usize = 100;
+const SCALE: f64 = 1e140;
+const EXPECTED: f64 = 5.0;
+const TERMS: usize = 100;
+
+/// Perform the Anderson–Darling test of `data` coming from `distribution`.
+///
+/// The p-value is computed by the approximation of Marsaglia and Marsaglia
+/// (2004), which includes a correction for the sample size. The distribution
+/// should be fully specified, that is, its parameters should not be estimated
+/// from the data.
+///
+/// For empty `data`, the statistic is zero and the p-value is one.
+pub fn anderson_darling<D>(data: &[f64], distribution: &D) -> Test
+where
+    D: Distribution,
+{
+    if data.is_empty() {
+        return Test {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let u = transform(data, distribution);
+    let n = u.len();
+    let sum = (0..n).fold(0.0, |sum, i| {
+        sum + (2 * i + 1) as f64 * (u[i].ln() + (1.0 - u[n - 1 - i]).ln())
+    });
+    let statistic = -(n as f64) - sum / n as f64;
+    let p_value = if statistic.is_finite() {
+        let x = anderson_darling_limit(statistic);
+        1.0 - x - anderson_darling_correction(n as f64, x)
+    } else {
+        0.0
+    };
+    Test {
+        statistic: statistic,
+        p_value: p_value.clamp(0.0, 1.0),
+    }
+}
+
+/// Perform Pearson’s chi-squared test of `counts` coming from `distribution`.
+///
+/// The element of `counts` at index `k` is the number of observations equal
+/// to `k`, and the last element also accounts for the probability of all
+/// larger values. Adjacent values are merged into bins until the expected
+/// count of each bin is at least five.
+pub fn chi_squared<D>(counts: &[usize], distribution: &D) -> Test
+where
+    D: Discrete,
+{
+    let m = counts.len();
+    let total = counts.iter().sum::<usize>() as f64;
+    should!(m > 0 && total > 0.0);
+    let mut bins: Vec<(f64, f64)> = vec![];
+    let (mut observed, mut expected, mut last) = (0.0, 0.0, 0.0);
+    for (k, &count) in counts.iter().enumerate() {
+        let p = if k + 1 < m {
+            let current = distribution.distribution(k as f64);
+            let p = current - last;
+            last = current;
+            p
+        } else if k > 0 {
+            distribution.survival((k - 1) as f64)
+        } else {
+            1.0
+        };
+        observed += count as f64;
+        expected += total * p;
+        if expected >= EXPECTED {
+            bins.push((observed, expected));
+            observed = 0.0;
+            expected = 0.0;
+        }
+    }
+    match bins.last_mut() {
+        Some(bin) => {
+            bin.0 += observed;
+            bin.1 += expected;
+        }
+        _ => bins.push((observed, expected)),
+    }
+    if bins.len() < 2 {
+        return Test {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let statistic = bins.iter().fold(0.0, |sum, &(observed, expected)| {
+        sum + (observed - expected).powi(2) / expected
+    });
+    Test {
+        statistic: statistic,
+        p_value: ChiSquared::new((bins.len() - 1) as f64).survival(statistic),
+    }
+}
+
+/// Perform the Cramér–von Mises test of `data` coming from `distribution`.
+///
+/// The p-value is computed from the limiting distribution of the statistic
+/// with the correction for the sample size of Csörgő and Faraway (1996). The
+/// distribution should be fully specified, that is, its parameters should not
+/// be estimated from the data.
+///
+/// For empty `data`, the statistic is zero and the p-value is one.
+pub fn cramer_von_mises<D>(data: &[f64], distribution: &D) -> Test
+where
+    D: Distribution,
+{
+    if data.is_empty() {
+        return Test {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let u = transform(data, distribution);
+    let n = u.len() as f64;
+    let statistic = u.iter().enumerate().fold(1.0 / (12.0 * n), |sum, (i, &u)| {
+        sum + ((2 * i + 1) as f64 / (2.0 * n) - u).powi(2)
+    });
+    let p_value = if statistic <= 1.0 / (12.0 * n) {
+        1.0
+    } else if statistic >= n / 3.0 {
+        0.0
+    } else {
+        1.0 - cramer_von_mises_limit(statistic) * (1.0 + 1.0 / (12.0 * n))
+            - cramer_von_mises_correction(statistic) / n
+    };
+    Test {
+        statistic: statistic,
+        p_value: p_value.clamp(0.0, 1.0),
+    }
+}
+
+/// Perform the Kolmogorov–Smirnov test of `data` coming from `distribution`.
+///
+/// For samples of up to 100 observations, the p-value is computed exactly by
+/// the method of Marsaglia, Tsang, and Wang (2003); otherwise, it is computed
+/// from the limiting Kolmogorov distribution with the correction of Stephens
+/// (1970). The distribution should be fully specified and continuous for the
+/// p-value to be exact; for discrete distributions, the test is conservative.
+///
+/// For empty `data`, the statistic is zero and the p-value is one.
+pub fn kolmogorov_smirnov<D>(data: &[f64], distribution: &D) -> Test
+where
+    D: Distribution,
+{
+    if data.is_empty() {
+        return Test {
+            statistic: 0.0,
+            p_value: 1.0,
+        };
+    }
+    let u = transform(data, distribution);
+    let n = u.len();
+    let statistic = u.iter().enumerate().fold(0.0, |statistic: f64, (i, &u)| {
+        let (lower, upper) = (i as f64 / n as f64, (i + 1) as f64 / n as f64);
+        statistic.max(upper - u).max(u - lower)
+    });
+    Test {
+        statistic: statistic,
+        p_value: kolmogorov(n, statistic).clamp(0.0, 1.0),
+    }
+}
+
+fn anderson_darling_correction(n: f64, x: f64) -> f64 {
+    let c = 0.01265 + 0.1757 / n;
+    if x < c {
+        let t = x / c;
+        let t = t.sqrt() * (1.0 - t) * (49.0 * t - 102.0);
+        t * (0.0037 / n.powi(3) + 0.00078 / n.powi(2) + 0.00006 / n)
+    } else if x < 0.8 {
+        let t = (x - c) / (0.8 - c);
+        let t =
+            -0.00022633 + (6.54034 - (14.6538 - (14.458 - (8.259 - 1.91864 * t) * t) * t) * t) * t;
+        t * (0.04213 / n + 0.01365 / n.powi(2))
+    } else {
+        let t = -130.2137
+            + (745.2337 - (1705.091 - (1950.646 - (1116.360 - 255.7844 * x) * x) * x) * x) * x;
+        t / n
+    }
+}
+
+fn anderson_darling_limit(z: f64) -> f64 {
+    if z < 2.0 {
+        (-1.2337141 / z).exp() / z.sqrt()
+            * (2.00012
+                + (0.247105 - (0.0649821 - (0.0347962 - (0.011672 - 0.00168691 * z) * z) * z) * z)
+                    * z)
+    } else {
+        let t = 1.0776
+            - (2.30695 - (0.43424 - (0.082433 - (0.008056 - 0.0003146 * z) * z) * z) * z) * z;
+        (-t.exp()).exp()
+    }
+}
+
+// Compute the modified Bessel function of the second kind of order `nu` by
+// integrating its integral representation.
+fn bessel(nu: f64, z: f64) -> f64 {
+    const STEP: f64 = 0.05;
+    let mut sum = 0.5 * (-z).exp();
+    let mut t = STEP;
+    loop {
+        let exponent = z * t.cosh();
+        if exponent > 745.0 {
+            break;
+        }
+        sum += (-exponent).exp() * (nu * t).cosh();
+        t += STEP;
+    }
+    sum * STEP
+}
+
+fn cramer_von_mises_correction(x: f64) -> f64 {
+    use std::f64::consts::PI;
+    let second = |y: f64| {
+        let z = y * y / 4.0;
+        (-z).exp() * (y / 2.0).powf(1.5) * (bessel(0.25, z) + bessel(0.75, z)) / PI.sqrt()
+    };
+    let third = |y: f64| {
+        let z = y * y / 4.0;
+        (-z).exp()
+            * (y / 2.0).powf(2.5)
+            * (2.0 * bessel(0.25, z) + 3.0 * bessel(0.75, z) - bessel(1.25, z))
+            / PI.sqrt()
+    };
+    let (root, y1, y2) = (2.0 * x.sqrt(), x.powf(0.75), x.powf(1.25));
+    let mut sum = 0.0;
+    for k in 0..TERMS {
+        let j = k as f64;
+        let m = 2.0 * j + 1.0;
+        // The ratio of Γ(k + 1/2) to Γ(k + 1).
+        let g = ((j + 0.5).ln_gamma().0 - (j + 1.0).ln_gamma().0).exp();
+        let term = m * g * second((4.0 * j + 3.0) / root) / (9.0 * y1)
+            + g * third((4.0 * j + 1.0) / root) / (72.0 * y2)
+            + 2.0 * (m + 2.0) * (j + 0.5) * g * third((4.0 * j + 5.0) / root) / (12.0 * y2)
+            + 7.0 * m * g * second((4.0 * j + 1.0) / root) / (144.0 * y1)
+            + 7.0 * m * g * second((4.0 * j + 5.0) / root) / (144.0 * y1);
+        sum -= term / PI;
+        if term.abs() < 1e-10 {
+            break;
+        }
+    }
+    sum
+}
+
+fn cramer_von_mises_limit(x: f64) -> f64 {
+    use std::f64::consts::PI;
+    let mut sum = 0.0;
+    for k in 0..TERMS {
+        let j = k as f64;
+        let g = ((j + 0.5).ln_gamma().0 - (j + 1.0).ln_gamma().0).exp();
+        let y = 4.0 * j + 1.0;
+        let q = y * y / (16.0 * x);
+        let term = g / (PI.powf(1.5) * x.sqrt()) * y.sqrt() * (-q).exp() * bessel(0.25, q);
+        sum += term;
+        if term.abs() < 1e-10 {
+            break;
+        }
+    }
+    sum
+}
+
+// Compute the probability of the Kolmogorov–Smirnov statistic exceeding `d`.
+fn kolmogorov(n: usize, d: f64) -> f64 {
+    if n == 0 {
+        return 1.0;
+    }
+    if d >= 1.0 {
+        return 0.0;
+    }
+    let m = n as f64;
+    if n <= EXACT {
+        let s = m * d * d;
+        if s > 7.24 || (s > 3.76 && n > 99) {
+            return 2.0 * (-(2.000071 + 0.331 / m.sqrt() + 1.409 / m) * s).exp();
+        }
+        return 1.0 - marsaglia(n, d);
+    }
+    let lambda = (m.sqrt() + 0.12 + 0.11 / m.sqrt()) * d;
+    let mut sum = 0.0;
+    for k in 1..(TERMS + 1) {
+        let term = (-2.0 * (k * k) as f64 * lambda * lambda).exp();
+        sum += if k % 2 == 1 { term } else { -term };
+        if term < 1e-16 {
+            break;
+        }
+    }
+    2.0 * sum
+}
+
+// Compute the probability of the Kolmogorov–Smirnov statistic being less than
+// `d` following Marsaglia, Tsang, and Wang (2003).
+fn marsaglia(n: usize, d: f64) -> f64 {
+    let k = (n as f64 * d) as usize + 1;
+    let m = 2 * k - 1;
+    let h = k as f64 - n as f64 * d;
+    let mut a = vec![0.0; m * m];
+    for i in 0..m {
+        for j in 0..m {
+            if i + 1 >= j {
+                a[i * m + j] = 1.0;
+            }
+        }
+    }
+    for i in 0..m {
+        a[i * m] -= h.powi(i as i32 + 1);
+        a[(m - 1) * m + i] -= h.powi((m - i) as i32);
+    }
+    if 2.0 * h - 1.0 > 0.0 {
+        a[(m - 1) * m] += (2.0 * h - 1.0).powi(m as i32);
+    }
+    for i in 0..m {
+        for j in 0..(i + 1) {
+            for g in 1..(i - j + 2) {
+                a[i * m + j] /= g as f64;
+            }
+        }
+    }
+    let (power, mut exponent) = power(&a, m, n);
+    let mut s = power[(k - 1) * m + k - 1];
+    for i in 1..(n + 1) {
+        s *= i as f64 / n as f64;
+        if s < 1.0 / SCALE {
+            s *= SCALE;
+            exponent -= 1;
+        }
+    }
+    s * SCALE.powi(exponent)
+}
+
+fn multiply(a: &[f64], b: &[f64], m: usize) -> Vec<f64> {
+    let mut c = vec![0.0; m * m];
+    for i in 0..m {
+        for l in 0..m {
+            let value = a[i * m + l];
+            if value != 0.0 {
+                for j in 0..m {
+                    c[i * m + j] += value * b[l * m + j];
+                }
+            }
+        }
+    }
+    c
+}
+
+// Raise the square matrix `a` of order `m` to the power `n`, which yields the
+// result scaled down by the returned power of the scale of `marsaglia`.
+fn power(a: &[f64], m: usize, n: usize) -> (Vec<f64>, i32) {
+    if n == 0 {
+        let mut b = vec![0.0; m * m];
+        for i in 0..m {
+            b[i * m + i] = 1.0;
+        }
+        return (b, 0);
+    }
+    if n == 1 {
+        return (a.to_vec(), 0);
+    }
+    let (half, exponent) = power(a, m, n / 2);
+    let mut b = multiply(&half, &half, m);
+    let mut exponent = 2 * exponent;
+    if n % 2 == 1 {
+        b = multiply(a, &b, m);
+    }
+    if b[m / 2 * (m + 1)] > SCALE {
+        for value in &mut b {
+            *value /= SCALE;
+        }
+        exponent += 1;
+    }
+    (b, exponent)
+}
+
+// Compute the sorted values of the distribution function at the data.
+fn transform<D>(data: &[f64], distribution: &D) -> Vec<f64>
+where
+    D: Distribution,
+{
+    let mut u = data
+        .iter()
+        .map(|&x| distribution.distribution(x))
+        .collect::<Vec<_>>();
+    u.sort_by(|x, y| x.partial_cmp(y).unwrap());
+    u
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use hypothesis;
+    use prelude::*;
+
+    const DATA: [f64; 8] = [0.9, 0.05, 0.35, 0.97, 0.2, 0.6, 0.4, 0.95];
+
+    #[test]
+    fn anderson_darling() {
+        let test = hypothesis::anderson_darling(&DATA, &Uniform::new(0.0, 1.0));
+        assert::close(test.statistic, 0.724289070223465, 1e-12);
+        assert::close(test.p_value, 0.5339400596927277, 1e-10);
+
+        let mut source = source::default();
+        let d = Gaussian::new(1.0, 2.0);
+        let data = Independent(&d, &mut source).take(1000).collect::<Vec<_>>();
+        assert!(hypothesis::anderson_darling(&data, &d).p_value > 0.01);
+        let d = Gaussian::new(1.5, 2.0);
+        assert!(hypothesis::anderson_darling(&data, &d).p_value < 1e-3);
+    }
+
+    #[test]
+    fn chi_squared() {
+        let d = Categorical::new(&[0.25; 4]);
+        let test = hypothesis::chi_squared(&[18, 22, 20, 40], &d);
+        assert::close(test.statistic, 12.32, 1e-12);
+        assert::close(test.p_value, 0.00636362999519527, 1e-12);
+
+        let test = hypothesis::chi_squared(&[1, 1, 1, 1], &d);
+        assert_eq!((test.statistic, test.p_value), (0.0, 1.0));
+
+        let mut source = source::default();
+        let d = Poisson::new(3.0);
+        let mut counts = vec![0; 20];
+        for x in Independent(&d, &mut source).take(1000) {
+            counts[x.min(19)] += 1;
+        }
+        assert!(hypothesis::chi_squared(&counts, &d).p_value > 0.01);
+        assert!(hypothesis::chi_squared(&counts, &Poisson::new(3.3)).p_value < 1e-3);
+        assert!(hypothesis::chi_squared(&counts, &Binomial::new(10, 0.3)).p_value < 1e-3);
+    }
+
+    #[test]
+    fn cramer_von_mises() {
+        let test = hypothesis::cramer_von_mises(&DATA, &Uniform::new(0.0, 1.0));
+        assert::close(test.statistic, 0.08006666666666666, 1e-12);
+        assert::close(test.p_value, 0.7048548047194526, 1e-8);
+
+        let mut source = source::default();
+        let d = Exponential::new(2.0);
+        let data = Independent(&d, &mut source).take(1000).collect::<Vec<_>>();
+        assert!(hypothesis::cramer_von_mises(&data, &d).p_value > 0.01);
+        let d = Exponential::new(2.3);
+        assert!(hypothesis::cramer_von_mises(&data, &d).p_value < 1e-3);
+    }
+
+    #[test]
+    fn empty() {
+        let d = Uniform::new(0.0, 1.0);
+        for test in &[
+            hypothesis::anderson_darling(&[], &d),
+            hypothesis::cramer_von_mises(&[], &d),
+            hypothesis::kolmogorov_smirnov(&[], &d),
+        ] {
+            assert_eq!((test.statistic, test.p_value), (0.0, 1.0));
+        }
+        assert_eq!(super::kolmogorov(0, 0.0), 1.0);
+        assert_eq!(super::power(&[2.0; 4], 2, 0), (vec![1.0, 0.0, 0.0, 1.0], 0));
+    }
+
+    #[test]
+    fn kolmogorov_smirnov() {
+        let test = hypothesis::kolmogorov_smirnov(&DATA, &Uniform::new(0.0, 1.0));
+        assert::close(test.statistic, 0.275, 1e-15);
+        assert::close(test.p_value, 0.4971549427490234, 1e-12);
+
+        assert::close(super::marsaglia(10, 0.274), 0.6284796154565043, 1e-14);
+        assert::close(super::kolmogorov(100, 0.1), 0.2526927570063904, 1e-12);
+        assert::close(super::kolmogorov(101, 0.1), 0.2510, 2e-3);
+
+        let mut source = source::default();
+        let d = Gaussian::new(0.0, 1.0);
+        let data = Independent(&d, &mut source).take(1000).collect::<Vec<_>>();
+        assert!(hypothesis::kolmogorov_smirnov(&data, &d).p_value > 0.01);
+        let d = Gaussian::new(0.2, 1.0);
+        assert!(hypothesis::kolmogorov_smirnov(&data, &d).p_value < 1e-3);
+    }
+}
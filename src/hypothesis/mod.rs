@@ -0,0 +1,47 @@
+//! Hypothesis tests.
+//!
+//! Each test returns the value of its statistic together with the p-value,
+//! that is, the probability of observing a value at least as extreme under
+//! the null hypothesis.
+
+/// An alternative hypothesis.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Alternative {
+    /// The parameter is less than its value under the null hypothesis.
+    Less,
+    /// The parameter is greater than its value under the null hypothesis.
+    Greater,
+    /// The parameter differs from its value under the null hypothesis.
+    TwoSided,
+}
+
+/// The result of a hypothesis test about a parameter.
+#[derive(Clone, Copy, Debug)]
+pub struct Inference {
+    /// The estimate of the parameter.
+    pub estimate: f64,
+    /// The value of the test statistic.
+    pub statistic: f64,
+    /// The p-value.
+    pub p_value: f64,
+    /// The confidence interval for the parameter.
+    pub interval: (f64, f64),
+}
+
+/// The result of a hypothesis test.
+#[derive(Clone, Copy, Debug)]
+pub struct Test {
+    /// The value of the test statistic.
+    pub statistic: f64,
+    /// The p-value.
+    pub p_value: f64,
+}
+
+mod goodness;
+mod parametric;
+
+pub use self::goodness::anderson_darling;
+pub use self::goodness::chi_squared;
+pub use self::goodness::cramer_von_mises;
+pub use self::goodness::kolmogorov_smirnov;
+pub use self::parametric::Tester;
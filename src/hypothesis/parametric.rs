@@ -0,0 +1,404 @@
+use distribution::{Beta, Binomial, Discrete, Distribution, Gaussian, Inverse, StudentsT};
+use hypothesis::{Alternative, Inference};
+
+/// A means of performing parametric tests.
+///
+/// The p-values are computed for the alternative hypothesis of the tester,
+/// and the confidence intervals are two-sided or one-sided accordingly.
+#[derive(Clone, Copy, Debug)]
+pub struct Tester {
+    /// The alternative hypothesis.
+    pub alternative: Alternative,
+    /// The confidence level of the confidence intervals.
+    pub confidence: f64,
+}
+
+impl Tester {
+    /// Create a tester for the alternative hypothesis `alternative` with the
+    /// confidence level of 0.95.
+    #[inline]
+    pub fn new(alternative: Alternative) -> Self {
+        Tester {
+            alternative: alternative,
+            confidence: 0.95,
+        }
+    }
+
+    /// Perform the exact binomial test of the probability of success being `p`
+    /// given `successes` out of `trials`.
+    ///
+    /// The statistic is the number of successes, and the confidence interval
+    /// is the one of Clopper and Pearson. It should hold that `0 < p < 1` and
+    /// `successes <= trials`.
+    pub fn exact_proportion(&self, successes: usize, trials: usize, p: f64) -> Inference {
+        should!(successes <= trials && trials > 0);
+        let (x, n) = (successes, trials);
+        let distribution = Binomial::new(n, p);
+        let p_value = match self.alternative {
+            Alternative::Less => distribution.distribution(x as f64),
+            Alternative::Greater if x > 0 => distribution.survival((x - 1) as f64),
+            Alternative::Greater => 1.0,
+            Alternative::TwoSided => {
+                let limit = distribution.mass(x) * (1.0 + 1e-7);
+                (0..(n + 1))
+                    .map(|k| distribution.mass(k))
+                    .filter(|&mass| mass <= limit)
+                    .sum()
+            }
+        };
+        let alpha = match self.alternative {
+            Alternative::TwoSided => (1.0 - self.confidence) / 2.0,
+            _ => 1.0 - self.confidence,
+        };
+        let lower = if x == 0 {
+            0.0
+        } else {
+            Beta::new(x as f64, (n - x + 1) as f64, 0.0, 1.0).inverse(alpha)
+        };
+        let upper = if x == n {
+            1.0
+        } else {
+            Beta::new((x + 1) as f64, (n - x) as f64, 0.0, 1.0).inverse(1.0 - alpha)
+        };
+        Inference {
+            estimate: x as f64 / n as f64,
+            statistic: x as f64,
+            p_value: p_value.min(1.0),
+            interval: self.limit((lower, upper), (0.0, 1.0)),
+        }
+    }
+
+    /// Perform the paired t-test of the mean difference between `x` and `y`
+    /// being `delta`.
+    ///
+    /// If the differences are constant, the statistic and the p-value are
+    /// defined as in `t`. It should hold that `x` and `y` have the same length
+    /// of at least two.
+    pub fn paired_t(&self, x: &[f64], y: &[f64], delta: f64) -> Inference {
+        should!(x.len() == y.len());
+        let difference = x.iter().zip(y).map(|(&x, &y)| x - y).collect::<Vec<_>>();
+        self.t(&difference, delta)
+    }
+
+    /// Perform the one-sample t-test of the mean of `data` being `mu`.
+    ///
+    /// If `data` is constant, the statistic is infinite unless the mean equals
+    /// `mu`, in which case it is zero, and the p-value is either zero or one.
+    /// It should hold that `data` has at least two elements.
+    pub fn t(&self, data: &[f64], mu: f64) -> Inference {
+        let n = data.len();
+        should!(n > 1);
+        let (mean, variance) = moments(data);
+        let error = (variance / n as f64).sqrt();
+        if error == 0.0 {
+            return self.degenerate(mean, mu);
+        }
+        let distribution = StudentsT::new((n - 1) as f64, 0.0, 1.0);
+        self.locate(mean, mu, error, &distribution)
+    }
+
+    /// Perform the two-sample z-test of the difference between the means of
+    /// `x` and `y` being `delta` given the standard deviations `sigma_x` and
+    /// `sigma_y` of the two populations.
+    ///
+    /// It should hold that `sigma_x > 0` and `sigma_y > 0`.
+    pub fn two_sample_z(
+        &self,
+        x: &[f64],
+        y: &[f64],
+        delta: f64,
+        sigma_x: f64,
+        sigma_y: f64,
+    ) -> Inference {
+        should!(!x.is_empty() && !y.is_empty() && sigma_x > 0.0 && sigma_y > 0.0);
+        let (nx, ny) = (x.len() as f64, y.len() as f64);
+        let error = (sigma_x * sigma_x / nx + sigma_y * sigma_y / ny).sqrt();
+        let distribution = Gaussian::new(0.0, 1.0);
+        self.locate(moments(x).0 - moments(y).0, delta, error, &distribution)
+    }
+
+    /// Perform the two-sample z-test of the probabilities of success being
+    /// equal given `x` and `y`, each of which is a pair of the number of
+    /// successes and the number of trials.
+    ///
+    /// The statistic uses the pooled estimate of the probability, and the
+    /// confidence interval for the difference is the one of Newcombe based on
+    /// the Wilson score intervals of the two probabilities.
+    pub fn two_proportions(&self, x: (usize, usize), y: (usize, usize)) -> Inference {
+        should!(x.0 <= x.1 && y.0 <= y.1 && x.1 > 0 && y.1 > 0);
+        let (px, py) = (x.0 as f64 / x.1 as f64, y.0 as f64 / y.1 as f64);
+        let (nx, ny) = (x.1 as f64, y.1 as f64);
+        let pooled = (x.0 + y.0) as f64 / (nx + ny);
+        let error = (pooled * (1.0 - pooled) * (1.0 / nx + 1.0 / ny)).sqrt();
+        let difference = px - py;
+        let statistic = if error > 0.0 { difference / error } else { 0.0 };
+        let distribution = Gaussian::new(0.0, 1.0);
+        let z = self.critical(&distribution);
+        let (lx, ux) = wilson(px, nx, z);
+        let (ly, uy) = wilson(py, ny, z);
+        let lower = difference - ((px - lx).powi(2) + (uy - py).powi(2)).sqrt();
+        let upper = difference + ((ux - px).powi(2) + (py - ly).powi(2)).sqrt();
+        Inference {
+            estimate: difference,
+            statistic: statistic,
+            p_value: self.p_value(statistic, &distribution),
+            interval: self.limit((lower, upper), (-1.0, 1.0)),
+        }
+    }
+
+    /// Perform Welch’s two-sample t-test of the difference between the means
+    /// of `x` and `y` being `delta` without assuming equal variances.
+    ///
+    /// If both `x` and `y` are constant, the statistic and the p-value are
+    /// defined as in `t`. It should hold that `x` and `y` have at least two
+    /// elements each.
+    pub fn welch_t(&self, x: &[f64], y: &[f64], delta: f64) -> Inference {
+        should!(x.len() > 1 && y.len() > 1);
+        let (nx, ny) = (x.len() as f64, y.len() as f64);
+        let ((mx, vx), (my, vy)) = (moments(x), moments(y));
+        let (sx, sy) = (vx / nx, vy / ny);
+        if sx + sy == 0.0 {
+            return self.degenerate(mx - my, delta);
+        }
+        let nu = (sx + sy).powi(2) / (sx * sx / (nx - 1.0) + sy * sy / (ny - 1.0));
+        let distribution = StudentsT::new(nu, 0.0, 1.0);
+        self.locate(mx - my, delta, (sx + sy).sqrt(), &distribution)
+    }
+
+    /// Perform the score test of the probability of success being `p` given
+    /// `successes` out of `trials`.
+    ///
+    /// The p-value is computed from the normal approximation, and the
+    /// confidence interval is the Wilson score interval. It should hold that
+    /// `0 < p < 1` and `successes <= trials`.
+    pub fn wilson_proportion(&self, successes: usize, trials: usize, p: f64) -> Inference {
+        should!(0.0 < p && p < 1.0 && successes <= trials && trials > 0);
+        let n = trials as f64;
+        let estimate = successes as f64 / n;
+        let statistic = (estimate - p) / (p * (1.0 - p) / n).sqrt();
+        let distribution = Gaussian::new(0.0, 1.0);
+        let interval = wilson(estimate, n, self.critical(&distribution));
+        Inference {
+            estimate: estimate,
+            statistic: statistic,
+            p_value: self.p_value(statistic, &distribution),
+            interval: self.limit(interval, (0.0, 1.0)),
+        }
+    }
+
+    /// Perform the one-sample z-test of the mean of `data` being `mu` given
+    /// the standard deviation `sigma` of the population.
+    ///
+    /// It should hold that `sigma > 0`.
+    pub fn z(&self, data: &[f64], mu: f64, sigma: f64) -> Inference {
+        should!(!data.is_empty() && sigma > 0.0);
+        let error = sigma / (data.len() as f64).sqrt();
+        let distribution = Gaussian::new(0.0, 1.0);
+        self.locate(moments(data).0, mu, error, &distribution)
+    }
+
+    fn critical<D>(&self, distribution: &D) -> f64
+    where
+        D: Inverse<Value = f64>,
+    {
+        should!(0.0 < self.confidence && self.confidence < 1.0);
+        match self.alternative {
+            Alternative::TwoSided => distribution.inverse((1.0 + self.confidence) / 2.0),
+            _ => distribution.inverse(self.confidence),
+        }
+    }
+
+    // Test a location parameter whose standard error is zero.
+    fn degenerate(&self, estimate: f64, null: f64) -> Inference {
+        use std::f64::INFINITY;
+        let statistic = if estimate == null {
+            0.0
+        } else {
+            (estimate - null).signum() * INFINITY
+        };
+        let p_value = match self.alternative {
+            Alternative::Less if statistic < 0.0 => 0.0,
+            Alternative::Greater if statistic > 0.0 => 0.0,
+            Alternative::TwoSided if statistic != 0.0 => 0.0,
+            _ => 1.0,
+        };
+        Inference {
+            estimate: estimate,
+            statistic: statistic,
+            p_value: p_value,
+            interval: self.limit((estimate, estimate), (-INFINITY, INFINITY)),
+        }
+    }
+
+    #[inline]
+    fn limit(&self, interval: (f64, f64), range: (f64, f64)) -> (f64, f64) {
+        match self.alternative {
+            Alternative::Less => (range.0, interval.1.min(range.1)),
+            Alternative::Greater => (interval.0.max(range.0), range.1),
+            Alternative::TwoSided => (interval.0.max(range.0), interval.1.min(range.1)),
+        }
+    }
+
+    // Test a location parameter whose estimate standardized by its standard
+    // error follows a symmetric distribution.
+    fn locate<D>(&self, estimate: f64, null: f64, error: f64, distribution: &D) -> Inference
+    where
+        D: Inverse<Value = f64>,
+    {
+        use std::f64::INFINITY;
+        let statistic = (estimate - null) / error;
+        let delta = error * self.critical(distribution);
+        Inference {
+            estimate: estimate,
+            statistic: statistic,
+            p_value: self.p_value(statistic, distribution),
+            interval: self.limit((estimate - delta, estimate + delta), (-INFINITY, INFINITY)),
+        }
+    }
+
+    // Compute the p-value of a statistic following a symmetric distribution.
+    fn p_value<D>(&self, statistic: f64, distribution: &D) -> f64
+    where
+        D: Distribution,
+    {
+        match self.alternative {
+            Alternative::Less => distribution.distribution(statistic),
+            Alternative::Greater => distribution.distribution(-statistic),
+            Alternative::TwoSided => (2.0 * distribution.distribution(-statistic.abs())).min(1.0),
+        }
+    }
+}
+
+fn moments(data: &[f64]) -> (f64, f64) {
+    let n = data.len() as f64;
+    let mean = data.iter().sum::<f64>() / n;
+    let variance = data.iter().map(|&x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0);
+    (mean, variance)
+}
+
+// Compute the Wilson score interval for the probability estimated by `p` from
+// `n` trials with the critical value `z`.
+fn wilson(p: f64, n: f64, z: f64) -> (f64, f64) {
+    let scale = 1.0 + z * z / n;
+    let center = (p + z * z / (2.0 * n)) / scale;
+    let delta = z / scale * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+    (center - delta, center + delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use hypothesis::{Alternative, Tester};
+
+    const X: [f64; 8] = [5.1, 4.9, 5.6, 5.8, 6.0, 5.3, 5.7, 6.2];
+    const Y: [f64; 8] = [4.8, 5.0, 5.2, 5.1, 5.5, 4.9, 5.3, 5.0];
+
+    #[test]
+    fn degenerate() {
+        use std::f64::INFINITY;
+
+        let tester = Tester::new(Alternative::TwoSided);
+        let inference = tester.t(&[2.0; 4], 2.0);
+        assert_eq!((inference.statistic, inference.p_value), (0.0, 1.0));
+        assert_eq!(inference.interval, (2.0, 2.0));
+        let inference = tester.t(&[2.0; 4], 1.0);
+        assert_eq!((inference.statistic, inference.p_value), (INFINITY, 0.0));
+
+        let inference = Tester::new(Alternative::Less).paired_t(&[3.0; 3], &[1.0; 3], 1.0);
+        assert_eq!((inference.statistic, inference.p_value), (INFINITY, 1.0));
+        assert_eq!(inference.interval, (-INFINITY, 2.0));
+
+        let inference = Tester::new(Alternative::Greater).welch_t(&[3.0; 3], &[1.0; 2], 1.0);
+        assert_eq!((inference.statistic, inference.p_value), (INFINITY, 0.0));
+        let inference = tester.welch_t(&[3.0; 3], &[1.0; 2], 3.0);
+        assert_eq!((inference.statistic, inference.p_value), (-INFINITY, 0.0));
+    }
+
+    #[test]
+    fn exact_proportion() {
+        let inference = Tester::new(Alternative::TwoSided).exact_proportion(7, 20, 0.5);
+        assert_eq!(inference.statistic, 7.0);
+        assert::close(inference.estimate, 0.35, 1e-15);
+        assert::close(inference.p_value, 0.26317596435546875, 1e-12);
+        assert::close(inference.interval.0, 0.153909204784541159, 1e-8);
+        assert::close(inference.interval.1, 0.592188534532828118, 1e-8);
+
+        let inference = Tester::new(Alternative::Less).exact_proportion(0, 10, 0.2);
+        assert::close(inference.p_value, 0.8f64.powi(10), 1e-12);
+        assert_eq!(inference.interval.0, 0.0);
+        assert::close(inference.interval.1, 1.0 - 0.05f64.powf(0.1), 1e-8);
+    }
+
+    #[test]
+    fn paired_t() {
+        let inference = Tester::new(Alternative::Greater).paired_t(&X, &Y, 0.0);
+        assert::close(inference.estimate, 0.475, 1e-12);
+        assert::close(inference.statistic, 3.637357075150389, 1e-10);
+        assert::close(inference.p_value, 0.004158105225262885, 1e-8);
+        assert::close(inference.interval.0, 0.227588261524875, 1e-6);
+        assert_eq!(inference.interval.1, ::std::f64::INFINITY);
+    }
+
+    #[test]
+    fn t() {
+        let inference = Tester::new(Alternative::TwoSided).t(&X, 5.0);
+        assert::close(inference.estimate, 5.575, 1e-12);
+        assert::close(inference.statistic, 3.643130718721435, 1e-10);
+        assert::close(inference.p_value, 0.00825274952300077, 1e-8);
+        assert::close(inference.interval.0, 5.20178831761957, 1e-6);
+        assert::close(inference.interval.1, 5.94821168238043, 1e-6);
+    }
+
+    #[test]
+    fn two_proportions() {
+        let inference = Tester::new(Alternative::TwoSided).two_proportions((45, 100), (30, 90));
+        assert::close(inference.estimate, 0.116666666666666667, 1e-12);
+        assert::close(inference.statistic, 1.642726612871929, 1e-10);
+        assert::close(inference.p_value, 0.1004395099796355, 1e-8);
+        assert::close(inference.interval.0, -0.0222698590731692594, 1e-8);
+        assert::close(inference.interval.1, 0.248582972309352032, 1e-8);
+    }
+
+    #[test]
+    fn two_sample_z() {
+        let inference = Tester::new(Alternative::TwoSided).two_sample_z(&X, &Y, 0.2, 0.5, 0.3);
+        assert::close(inference.estimate, 0.475, 1e-12);
+        assert::close(inference.statistic, 1.3339459376998314, 1e-10);
+        assert::close(inference.p_value, 0.18222157493333112, 1e-8);
+        assert::close(inference.interval.0, 0.07094307346674485, 1e-8);
+        assert::close(inference.interval.1, 0.87905692653325515, 1e-8);
+    }
+
+    #[test]
+    fn welch_t() {
+        let inference = Tester::new(Alternative::TwoSided).welch_t(&X, &Y, 0.0);
+        assert::close(inference.estimate, 0.475, 1e-12);
+        assert::close(inference.statistic, 2.683175394831691, 1e-10);
+        assert::close(inference.p_value, 0.02227305734961772, 1e-8);
+        assert::close(inference.interval.0, 0.0825392597154904, 1e-6);
+        assert::close(inference.interval.1, 0.8674607402845096, 1e-6);
+    }
+
+    #[test]
+    fn wilson_proportion() {
+        let inference = Tester::new(Alternative::TwoSided).wilson_proportion(7, 20, 0.5);
+        assert::close(inference.statistic, -1.3416407864998738, 1e-12);
+        assert::close(inference.p_value, 0.17971249487899984, 1e-8);
+        assert::close(inference.interval.0, 0.18119182410108205, 1e-8);
+        assert::close(inference.interval.1, 0.56714572331476377, 1e-8);
+    }
+
+    #[test]
+    fn z() {
+        let mut tester = Tester::new(Alternative::Less);
+        let inference = tester.z(&X, 5.5, 0.5);
+        assert::close(inference.statistic, 0.4242640687119285, 1e-10);
+        assert::close(inference.p_value, 0.6643133797295637, 1e-8);
+        assert_eq!(inference.interval.0, -::std::f64::INFINITY);
+        assert::close(inference.interval.1, 5.865771788419169, 1e-8);
+
+        tester.confidence = 0.99;
+        let wide = tester.z(&X, 5.5, 0.5);
+        assert!(wide.interval.1 > inference.interval.1);
+    }
+}
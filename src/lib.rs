@@ -13,8 +13,14 @@
 
 #[cfg(test)]
 extern crate assert;
+#[cfg(all(test, feature = "serde"))]
+extern crate serde_json;
 
+#[cfg(feature = "rand")]
+extern crate rand;
 extern crate random;
+#[cfg(feature = "serde")]
+extern crate serde;
 extern crate special;
 
 macro_rules! nonnan(
@@ -26,7 +32,12 @@ macro_rules! should(
     ($requirement:expr, $code:expr) => (debug_assert!($code, stringify!($requirement)));
 );
 
+pub mod diagnostics;
 pub mod distribution;
+pub mod hypothesis;
+pub mod mcmc;
+pub mod montecarlo;
 pub mod prelude;
+pub mod qmc;
 pub mod sampler;
 pub mod source;
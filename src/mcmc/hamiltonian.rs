@@ -0,0 +1,337 @@
+use distribution::{Gaussian, Sample, Uniform};
+use mcmc::{Differentiable, Kernel};
+use source::Source;
+
+/// A Hamiltonian Monte Carlo kernel.
+///
+/// The trajectories are simulated by the leapfrog integrator with the identity
+/// mass matrix. The number of leapfrog steps is either fixed or determined by
+/// the No-U-Turn criterion, in which case the trajectory is doubled until it
+/// starts to turn back on itself. During the warm-up, the step size is
+/// adapted by dual averaging toward the acceptance statistic of 0.8.
+#[derive(Clone, Debug)]
+pub struct Hamiltonian {
+    step: f64,
+    steps: Option<usize>,
+    statistic: f64,
+    mu: f64,
+    h: f64,
+    ln_step: Option<f64>,
+}
+
+/// The maximal depth of the trajectories built by the No-U-Turn criterion.
+pub const DEPTH: usize = 10;
+
+const DIVERGENCE: f64 = 1000.0;
+const GAMMA: f64 = 0.05;
+const KAPPA: f64 = 0.75;
+const T0: f64 = 10.0;
+const TARGET: f64 = 0.8;
+
+#[derive(Clone)]
+struct State {
+    x: Vec<f64>,
+    r: Vec<f64>,
+    g: Vec<f64>,
+    ln_p: f64,
+}
+
+struct Tree {
+    minus: State,
+    plus: State,
+    proposal: State,
+    count: usize,
+    proceed: bool,
+    statistic: f64,
+    size: usize,
+}
+
+impl Hamiltonian {
+    /// Create a kernel with initial step size `step` taking `steps` leapfrog
+    /// steps per transition.
+    ///
+    /// It should hold that `step > 0` and `steps > 0`.
+    #[inline]
+    pub fn new(step: f64, steps: usize) -> Self {
+        should!(steps > 0);
+        Hamiltonian {
+            steps: Some(steps),
+            ..Hamiltonian::nuts(step)
+        }
+    }
+
+    /// Create a kernel with initial step size `step` using the No-U-Turn
+    /// criterion.
+    ///
+    /// It should hold that `step > 0`.
+    #[inline]
+    pub fn nuts(step: f64) -> Self {
+        should!(step > 0.0);
+        Hamiltonian {
+            step: step,
+            steps: None,
+            statistic: 0.0,
+            mu: (10.0 * step).ln(),
+            h: 0.0,
+            ln_step: None,
+        }
+    }
+}
+
+impl<T> Kernel<T> for Hamiltonian
+where
+    T: Differentiable + ?Sized,
+{
+    fn transition<S>(&mut self, target: &T, x: &mut [f64], ln_p: &mut f64, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        let (gaussian, uniform) = (Gaussian::new(0.0, 1.0), Uniform::new(0.0, 1.0));
+        let mut g = vec![0.0; x.len()];
+        let state = State {
+            ln_p: target.ln_density_gradient(x, &mut g),
+            x: x.to_vec(),
+            r: x.iter().map(|_| gaussian.sample(source)).collect(),
+            g: g,
+        };
+        let joint = state.joint();
+        let statistic = match self.steps {
+            Some(steps) => {
+                let mut proposal = state;
+                for _ in 0..steps {
+                    leapfrog(target, &mut proposal, self.step);
+                }
+                let statistic = ratio(proposal.joint() - joint);
+                if uniform.sample(source) < statistic {
+                    x.copy_from_slice(&proposal.x);
+                    *ln_p = proposal.ln_p;
+                }
+                statistic
+            }
+            _ => {
+                let ln_u = joint + uniform.sample(source).ln();
+                let (mut minus, mut plus) = (state.clone(), state);
+                let (mut count, mut statistic, mut size) = (1, 0.0, 0);
+                for depth in 0..DEPTH {
+                    let forward = uniform.sample(source) < 0.5;
+                    let tree = if forward {
+                        build(target, &plus, self.step, depth, ln_u, joint, source)
+                    } else {
+                        build(target, &minus, -self.step, depth, ln_u, joint, source)
+                    };
+                    if tree.proceed && uniform.sample(source) * (count as f64) < tree.count as f64 {
+                        x.copy_from_slice(&tree.proposal.x);
+                        *ln_p = tree.proposal.ln_p;
+                    }
+                    count += tree.count;
+                    statistic += tree.statistic;
+                    size += tree.size;
+                    if forward {
+                        plus = tree.plus;
+                    } else {
+                        minus = tree.minus;
+                    }
+                    if !tree.proceed || turned(&minus, &plus) {
+                        break;
+                    }
+                }
+                statistic / size as f64
+            }
+        };
+        self.statistic = statistic;
+        statistic
+    }
+
+    fn adapt(&mut self, iteration: usize) {
+        let m = (iteration + 1) as f64;
+        self.h += (TARGET - self.statistic - self.h) / (m + T0);
+        let ln_step = self.mu - m.sqrt() / GAMMA * self.h;
+        let weight = m.powf(-KAPPA);
+        self.ln_step = Some(weight * ln_step + (1.0 - weight) * self.ln_step.unwrap_or(0.0));
+        self.step = ln_step.exp();
+    }
+
+    #[inline]
+    fn finish(&mut self) {
+        if let Some(ln_step) = self.ln_step {
+            self.step = ln_step.exp();
+        }
+    }
+
+    #[inline]
+    fn step(&self) -> f64 {
+        self.step
+    }
+}
+
+impl State {
+    #[inline]
+    fn joint(&self) -> f64 {
+        self.ln_p - 0.5 * dot(&self.r, &self.r)
+    }
+}
+
+fn build<T, S>(
+    target: &T,
+    state: &State,
+    step: f64,
+    depth: usize,
+    ln_u: f64,
+    joint: f64,
+    source: &mut S,
+) -> Tree
+where
+    T: Differentiable + ?Sized,
+    S: Source,
+{
+    if depth == 0 {
+        let mut state = state.clone();
+        leapfrog(target, &mut state, step);
+        let value = state.joint();
+        return Tree {
+            minus: state.clone(),
+            plus: state.clone(),
+            proposal: state,
+            count: usize::from(ln_u <= value),
+            proceed: ln_u < value + DIVERGENCE,
+            statistic: ratio(value - joint),
+            size: 1,
+        };
+    }
+    let mut tree = build(target, state, step, depth - 1, ln_u, joint, source);
+    if !tree.proceed {
+        return tree;
+    }
+    let other = {
+        let state = if step > 0.0 { &tree.plus } else { &tree.minus };
+        build(target, state, step, depth - 1, ln_u, joint, source)
+    };
+    let count = tree.count + other.count;
+    if count > 0 && Uniform::new(0.0, 1.0).sample(source) * (count as f64) < other.count as f64 {
+        tree.proposal = other.proposal;
+    }
+    if step > 0.0 {
+        tree.plus = other.plus;
+    } else {
+        tree.minus = other.minus;
+    }
+    tree.count = count;
+    tree.proceed = other.proceed && !turned(&tree.minus, &tree.plus);
+    tree.statistic += other.statistic;
+    tree.size += other.size;
+    tree
+}
+
+#[inline]
+fn dot(x: &[f64], y: &[f64]) -> f64 {
+    x.iter().zip(y).fold(0.0, |sum, (&x, &y)| sum + x * y)
+}
+
+fn leapfrog<T>(target: &T, state: &mut State, step: f64)
+where
+    T: Differentiable + ?Sized,
+{
+    for (r, &g) in state.r.iter_mut().zip(&state.g) {
+        *r += 0.5 * step * g;
+    }
+    for (x, &r) in state.x.iter_mut().zip(&state.r) {
+        *x += step * r;
+    }
+    state.ln_p = target.ln_density_gradient(&state.x, &mut state.g);
+    for (r, &g) in state.r.iter_mut().zip(&state.g) {
+        *r += 0.5 * step * g;
+    }
+}
+
+#[inline]
+fn ratio(difference: f64) -> f64 {
+    if difference.is_nan() {
+        0.0
+    } else {
+        difference.exp().min(1.0)
+    }
+}
+
+fn turned(minus: &State, plus: &State) -> bool {
+    let dx = plus
+        .x
+        .iter()
+        .zip(&minus.x)
+        .map(|(&plus, &minus)| plus - minus)
+        .collect::<Vec<_>>();
+    dot(&dx, &minus.r) < 0.0 || dot(&dx, &plus.r) < 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use mcmc::{Differentiable, Hamiltonian, Runner, Target};
+    use prelude::*;
+
+    struct Model {
+        x: Gaussian,
+        y: Gamma,
+    }
+
+    impl Target for Model {
+        fn ln_density(&self, x: &[f64]) -> f64 {
+            self.x.ln_density(x[0]) + self.y.ln_density(x[1])
+        }
+    }
+
+    impl Differentiable for Model {
+        fn ln_density_gradient(&self, x: &[f64], gradient: &mut [f64]) -> f64 {
+            gradient[0] = self.x.gradient(x[0]);
+            gradient[1] = self.y.gradient(x[1]);
+            self.ln_density(x)
+        }
+    }
+
+    fn model() -> Model {
+        Model {
+            x: Gaussian::new(1.0, 2.0),
+            y: Gamma::new(3.0, 2.0),
+        }
+    }
+
+    fn moments(draws: &[Vec<f64>], i: usize) -> (f64, f64) {
+        let n = draws.len() as f64;
+        let mean = draws.iter().map(|x| x[i]).sum::<f64>() / n;
+        let variance = draws.iter().map(|x| (x[i] - mean).powi(2)).sum::<f64>() / n;
+        (mean, variance)
+    }
+
+    #[test]
+    fn new() {
+        let mut source = source::default();
+        let target = model();
+        let runner = Runner::new(Hamiltonian::new(0.1, 10), 1000, 10000);
+        let chain = runner.run(&target, &[0.0, 1.0], &mut source);
+        assert!(chain.draws.iter().all(|x| x[1] > 0.0));
+        let (mean, variance) = moments(&chain.draws, 0);
+        assert::close(mean, 1.0, 0.2);
+        assert::close(variance, 4.0, 0.5);
+        let (mean, variance) = moments(&chain.draws, 1);
+        assert::close(mean, 6.0, 0.5);
+        assert::close(variance, 12.0, 2.0);
+        assert::close(chain.acceptance, 0.8, 0.1);
+    }
+
+    #[test]
+    fn nuts() {
+        let mut source = source::default();
+        let target = model();
+        let runner = Runner::new(Hamiltonian::nuts(1.0), 1000, 5000);
+        let chains = runner.run_chains(&target, &[vec![0.0, 1.0], vec![5.0, 10.0]], &mut source);
+        for chain in &chains {
+            assert!(chain.draws.iter().all(|x| x[1] > 0.0));
+            let (mean, variance) = moments(&chain.draws, 0);
+            assert::close(mean, 1.0, 0.2);
+            assert::close(variance, 4.0, 0.5);
+            let (mean, variance) = moments(&chain.draws, 1);
+            assert::close(mean, 6.0, 0.5);
+            assert::close(variance, 12.0, 2.0);
+            assert::close(chain.acceptance, 0.8, 0.1);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use distribution::{Gaussian, Sample, Uniform};
+use mcmc::{self, Kernel, Target};
+use source::Source;
+
+/// A random-walk Metropolis–Hastings kernel.
+///
+/// The proposals are drawn from a Gaussian distribution centered at the
+/// current point with the step size as the standard deviation in each
+/// coordinate. During the warm-up, the step size is adapted toward the
+/// acceptance rate of 0.44 for one dimension and 0.234 for more.
+#[derive(Clone, Debug)]
+pub struct Metropolis {
+    step: f64,
+    statistic: f64,
+    target: f64,
+}
+
+impl Metropolis {
+    /// Create a kernel with initial step size `step`.
+    ///
+    /// It should hold that `step > 0`.
+    #[inline]
+    pub fn new(step: f64) -> Self {
+        should!(step > 0.0);
+        Metropolis {
+            step: step,
+            statistic: 0.0,
+            target: 0.0,
+        }
+    }
+}
+
+impl<T> Kernel<T> for Metropolis
+where
+    T: Target + ?Sized,
+{
+    fn transition<S>(&mut self, target: &T, x: &mut [f64], ln_p: &mut f64, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        let proposal = Gaussian::new(0.0, self.step);
+        let y = x
+            .iter()
+            .map(|&x| x + proposal.sample(source))
+            .collect::<Vec<_>>();
+        let ln_q = target.ln_density(&y);
+        let statistic = if ln_q.is_nan() {
+            0.0
+        } else {
+            (ln_q - *ln_p).exp().min(1.0)
+        };
+        if statistic > 0.0 && Uniform::new(0.0, 1.0).sample(source) < statistic {
+            x.copy_from_slice(&y);
+            *ln_p = ln_q;
+        }
+        self.statistic = statistic;
+        self.target = if x.len() == 1 { 0.44 } else { 0.234 };
+        statistic
+    }
+
+    #[inline]
+    fn adapt(&mut self, iteration: usize) {
+        self.step = mcmc::adapt(self.step, iteration, self.statistic, self.target);
+    }
+
+    #[inline]
+    fn step(&self) -> f64 {
+        self.step
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use mcmc::{Metropolis, Runner};
+    use prelude::*;
+
+    #[test]
+    fn beta() {
+        let mut source = source::default();
+        let d = Beta::new(3.0, 5.0, 0.0, 1.0);
+        let target = |x: &[f64]| {
+            if 0.0 < x[0] && x[0] < 1.0 {
+                d.ln_density(x[0])
+            } else {
+                ::std::f64::NEG_INFINITY
+            }
+        };
+        let chain =
+            Runner::new(Metropolis::new(1.0), 1000, 20000).run(&target, &[0.5], &mut source);
+        let n = chain.draws.len() as f64;
+        let mean = chain.draws.iter().map(|x| x[0]).sum::<f64>() / n;
+        assert!(chain.draws.iter().all(|x| 0.0 < x[0] && x[0] < 1.0));
+        assert::close(mean, d.mean(), 1e-2);
+        assert::close(chain.acceptance, 0.44, 0.1);
+    }
+
+    #[test]
+    fn gaussian() {
+        let mut source = source::default();
+        let target = |x: &[f64]| -0.5 * (x[0] * x[0] + (x[1] - 1.0) * (x[1] - 1.0) / 4.0);
+        let chain =
+            Runner::new(Metropolis::new(0.1), 2000, 40000).run(&target, &[0.0, 0.0], &mut source);
+        let n = chain.draws.len() as f64;
+        let mean = chain.draws.iter().map(|x| x[1]).sum::<f64>() / n;
+        let variance = chain.draws.iter().map(|x| x[0] * x[0]).sum::<f64>() / n;
+        assert::close(mean, 1.0, 0.1);
+        assert::close(variance, 1.0, 0.1);
+        assert::close(chain.acceptance, 0.234, 0.1);
+    }
+}
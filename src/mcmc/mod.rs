@@ -0,0 +1,174 @@
+//! Markov chain Monte Carlo.
+//!
+//! A chain is defined by a target, which is a distribution over real vectors
+//! given by its log-density up to an additive constant, and a transition
+//! kernel. A `Runner` starts chains at given points, adapts the step size of
+//! the kernel during a warm-up period, and collects the subsequent draws.
+//! Kernels based on Hamiltonian dynamics additionally require the gradient of
+//! the log-density, for which the distributions implementing `Gradient` can
+//! serve as building blocks.
+
+use source::Source;
+
+/// The result of running a Markov chain.
+#[derive(Clone, Debug)]
+pub struct Chain {
+    /// The draws made after the warm-up.
+    pub draws: Vec<Vec<f64>>,
+    /// The average acceptance statistic after the warm-up.
+    pub acceptance: f64,
+    /// The step size after the warm-up.
+    pub step: f64,
+}
+
+/// A target capable of computing the gradient of the log-density.
+pub trait Differentiable: Target {
+    /// Compute the log-density and write its gradient into `gradient`.
+    fn ln_density_gradient(&self, x: &[f64], gradient: &mut [f64]) -> f64;
+}
+
+/// A transition kernel of a Markov chain.
+pub trait Kernel<T>
+where
+    T: Target + ?Sized,
+{
+    /// Make a transition from `x`, whose log-density is `ln_p`, updating both
+    /// in place, and return the acceptance statistic, which lies between zero
+    /// and one.
+    fn transition<S>(&mut self, target: &T, x: &mut [f64], ln_p: &mut f64, source: &mut S) -> f64
+    where
+        S: Source;
+
+    /// Adapt the step size given the last transition made at warm-up
+    /// iteration `iteration`.
+    fn adapt(&mut self, iteration: usize);
+
+    /// Conclude the adaptation at the end of the warm-up.
+    #[inline]
+    fn finish(&mut self) {}
+
+    /// Return the step size.
+    fn step(&self) -> f64;
+}
+
+/// A runner of Markov chains.
+#[derive(Clone, Copy, Debug)]
+pub struct Runner<K> {
+    /// The transition kernel.
+    pub kernel: K,
+    /// The number of warm-up iterations, during which the step size is
+    /// adapted and the draws are discarded.
+    pub warmup: usize,
+    /// The number of draws.
+    pub draws: usize,
+}
+
+/// A distribution given by its log-density up to an additive constant.
+///
+/// The trait is implemented for closures taking a point and returning the
+/// log-density, which may be negative infinity outside the support.
+pub trait Target {
+    /// Compute the log-density.
+    fn ln_density(&self, x: &[f64]) -> f64;
+}
+
+impl<K> Runner<K> {
+    /// Create a runner.
+    ///
+    /// It should hold that `draws > 0`.
+    #[inline]
+    pub fn new(kernel: K, warmup: usize, draws: usize) -> Self {
+        should!(draws > 0);
+        Runner {
+            kernel: kernel,
+            warmup: warmup,
+            draws: draws,
+        }
+    }
+
+    /// Run a chain starting at `x`.
+    ///
+    /// The log-density at `x` should be finite.
+    pub fn run<T, S>(&self, target: &T, x: &[f64], source: &mut S) -> Chain
+    where
+        K: Clone + Kernel<T>,
+        T: Target + ?Sized,
+        S: Source,
+    {
+        let mut kernel = self.kernel.clone();
+        let mut x = x.to_vec();
+        let mut ln_p = target.ln_density(&x);
+        should!(ln_p.is_finite());
+        for iteration in 0..self.warmup {
+            kernel.transition(target, &mut x, &mut ln_p, source);
+            kernel.adapt(iteration);
+        }
+        kernel.finish();
+        let mut draws = Vec::with_capacity(self.draws);
+        let mut acceptance = 0.0;
+        for _ in 0..self.draws {
+            acceptance += kernel.transition(target, &mut x, &mut ln_p, source);
+            draws.push(x.clone());
+        }
+        Chain {
+            draws: draws,
+            acceptance: acceptance / self.draws as f64,
+            step: kernel.step(),
+        }
+    }
+
+    /// Run a chain starting at each of the points in `xs`.
+    pub fn run_chains<T, S>(&self, target: &T, xs: &[Vec<f64>], source: &mut S) -> Vec<Chain>
+    where
+        K: Clone + Kernel<T>,
+        T: Target + ?Sized,
+        S: Source,
+    {
+        xs.iter().map(|x| self.run(target, x, source)).collect()
+    }
+}
+
+impl<F> Target for F
+where
+    F: Fn(&[f64]) -> f64,
+{
+    #[inline(always)]
+    fn ln_density(&self, x: &[f64]) -> f64 {
+        self(x)
+    }
+}
+
+mod hamiltonian;
+mod metropolis;
+mod slice;
+
+pub use self::hamiltonian::Hamiltonian;
+pub use self::metropolis::Metropolis;
+pub use self::slice::{MultivariateSlice, Slice};
+
+// Adapt a step size by a Robbins–Monro recursion on its logarithm, so that the
+// statistic approaches the target.
+fn adapt(step: f64, iteration: usize, statistic: f64, target: f64) -> f64 {
+    step * ((statistic - target) / (iteration as f64 + 1.0).powf(0.6)).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use mcmc::{Metropolis, Runner};
+    use prelude::*;
+
+    #[test]
+    fn run_chains() {
+        let mut source = source::default();
+        let target = |x: &[f64]| -0.5 * x[0] * x[0];
+        let runner = Runner::new(Metropolis::new(0.1), 500, 200);
+        let chains = runner.run_chains(&target, &[vec![-5.0], vec![5.0]], &mut source);
+        assert_eq!(chains.len(), 2);
+        for chain in &chains {
+            assert_eq!(chain.draws.len(), 200);
+            assert!(chain.step > 0.1);
+            assert!(0.2 < chain.acceptance && chain.acceptance < 0.7);
+        }
+        assert!(chains[0].draws != chains[1].draws);
+    }
+}
@@ -0,0 +1,228 @@
+use distribution::{Sample, Uniform};
+use mcmc::{self, Kernel, Target};
+use source::Source;
+
+/// A multivariate slice-sampling kernel.
+///
+/// Each transition draws a point uniformly from a hyperrectangle with sides
+/// of length equal to the step size placed at random around the current
+/// point and shrinks the hyperrectangle toward the current point until the
+/// point falls within the slice. During the warm-up, the step size is adapted
+/// so that about one shrinkage is needed per transition.
+#[derive(Clone, Debug)]
+pub struct MultivariateSlice {
+    width: f64,
+    statistic: f64,
+}
+
+/// A univariate slice-sampling kernel.
+///
+/// Each transition updates the coordinates one at a time by drawing from the
+/// slice along the coordinate, which is located by stepping out with the
+/// step size as the width and then sampled with shrinkage. During the
+/// warm-up, the step size is adapted so that steppings out and shrinkages
+/// occur about equally often.
+#[derive(Clone, Debug)]
+pub struct Slice {
+    width: f64,
+    statistic: f64,
+}
+
+/// The maximal number of steppings out in each direction.
+pub const STEPS: usize = 100;
+
+impl MultivariateSlice {
+    /// Create a kernel with initial step size `width`.
+    ///
+    /// It should hold that `width > 0`.
+    #[inline]
+    pub fn new(width: f64) -> Self {
+        should!(width > 0.0);
+        MultivariateSlice {
+            width: width,
+            statistic: 0.5,
+        }
+    }
+}
+
+impl<T> Kernel<T> for MultivariateSlice
+where
+    T: Target + ?Sized,
+{
+    fn transition<S>(&mut self, target: &T, x: &mut [f64], ln_p: &mut f64, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        let uniform = Uniform::new(0.0, 1.0);
+        let ln_y = *ln_p + uniform.sample(source).ln();
+        let mut bounds = x
+            .iter()
+            .map(|&x| {
+                let a = x - self.width * uniform.sample(source);
+                (a, a + self.width)
+            })
+            .collect::<Vec<_>>();
+        let mut y = x.to_vec();
+        let mut shrinkages = 0;
+        loop {
+            for (y, &(a, b)) in y.iter_mut().zip(&bounds) {
+                *y = a + (b - a) * uniform.sample(source);
+            }
+            let ln_q = target.ln_density(&y);
+            if ln_q > ln_y {
+                x.copy_from_slice(&y);
+                *ln_p = ln_q;
+                break;
+            }
+            shrinkages += 1;
+            let mut collapsed = true;
+            for ((bound, &x), &y) in bounds.iter_mut().zip(x.iter()).zip(&y) {
+                if y < x {
+                    bound.0 = y;
+                } else {
+                    bound.1 = y;
+                }
+                collapsed &= bound.0 >= bound.1;
+            }
+            if collapsed {
+                break;
+            }
+        }
+        self.statistic = 1.0 / (1.0 + shrinkages as f64);
+        1.0
+    }
+
+    #[inline]
+    fn adapt(&mut self, iteration: usize) {
+        self.width = mcmc::adapt(self.width, iteration, self.statistic, 0.5);
+    }
+
+    #[inline]
+    fn step(&self) -> f64 {
+        self.width
+    }
+}
+
+impl Slice {
+    /// Create a kernel with initial step size `width`.
+    ///
+    /// It should hold that `width > 0`.
+    #[inline]
+    pub fn new(width: f64) -> Self {
+        should!(width > 0.0);
+        Slice {
+            width: width,
+            statistic: 0.5,
+        }
+    }
+}
+
+impl<T> Kernel<T> for Slice
+where
+    T: Target + ?Sized,
+{
+    fn transition<S>(&mut self, target: &T, x: &mut [f64], ln_p: &mut f64, source: &mut S) -> f64
+    where
+        S: Source,
+    {
+        let uniform = Uniform::new(0.0, 1.0);
+        let (mut expansions, mut shrinkages) = (0, 0);
+        let mut y = x.to_vec();
+        for i in 0..x.len() {
+            let ln_y = *ln_p + uniform.sample(source).ln();
+            let mut a = x[i] - self.width * uniform.sample(source);
+            let mut b = a + self.width;
+            for _ in 0..STEPS {
+                y[i] = a;
+                if target.ln_density(&y) <= ln_y {
+                    break;
+                }
+                a -= self.width;
+                expansions += 1;
+            }
+            for _ in 0..STEPS {
+                y[i] = b;
+                if target.ln_density(&y) <= ln_y {
+                    break;
+                }
+                b += self.width;
+                expansions += 1;
+            }
+            loop {
+                y[i] = a + (b - a) * uniform.sample(source);
+                let ln_q = target.ln_density(&y);
+                if ln_q > ln_y {
+                    x[i] = y[i];
+                    *ln_p = ln_q;
+                    break;
+                }
+                shrinkages += 1;
+                if y[i] < x[i] {
+                    a = y[i];
+                } else {
+                    b = y[i];
+                }
+                if a >= b {
+                    y[i] = x[i];
+                    break;
+                }
+            }
+        }
+        self.statistic = if expansions + shrinkages > 0 {
+            expansions as f64 / (expansions + shrinkages) as f64
+        } else {
+            0.5
+        };
+        1.0
+    }
+
+    #[inline]
+    fn adapt(&mut self, iteration: usize) {
+        self.width = mcmc::adapt(self.width, iteration, self.statistic, 0.5);
+    }
+
+    #[inline]
+    fn step(&self) -> f64 {
+        self.width
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use mcmc::{MultivariateSlice, Runner, Slice};
+    use prelude::*;
+
+    #[test]
+    fn multivariate_slice() {
+        let mut source = source::default();
+        let target = |x: &[f64]| -0.5 * (x[0] * x[0] + (x[1] - 1.0) * (x[1] - 1.0) / 4.0);
+        let runner = Runner::new(MultivariateSlice::new(10.0), 1000, 20000);
+        let chain = runner.run(&target, &[0.0, 0.0], &mut source);
+        let n = chain.draws.len() as f64;
+        let mean = chain.draws.iter().map(|x| x[1]).sum::<f64>() / n;
+        let variance = chain.draws.iter().map(|x| x[0] * x[0]).sum::<f64>() / n;
+        assert_eq!(chain.acceptance, 1.0);
+        assert::close(mean, 1.0, 0.1);
+        assert::close(variance, 1.0, 0.1);
+    }
+
+    #[test]
+    fn slice() {
+        let mut source = source::default();
+        let d = Gamma::new(3.0, 2.0);
+        let target = |x: &[f64]| {
+            if x[0] > 0.0 {
+                d.ln_density(x[0])
+            } else {
+                ::std::f64::NEG_INFINITY
+            }
+        };
+        let chain = Runner::new(Slice::new(0.1), 1000, 20000).run(&target, &[1.0], &mut source);
+        let n = chain.draws.len() as f64;
+        let mean = chain.draws.iter().map(|x| x[0]).sum::<f64>() / n;
+        assert!(chain.draws.iter().all(|x| x[0] > 0.0));
+        assert!(chain.step > 0.1);
+        assert::close(mean, d.mean(), 0.15);
+    }
+}
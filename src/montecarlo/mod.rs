@@ -0,0 +1,238 @@
+//! Monte Carlo estimation of expected values.
+
+use distribution::{Inverse, Sample, StudentsT};
+use source::Source;
+
+/// The result of estimating an expected value.
+#[derive(Clone, Copy, Debug)]
+pub struct Estimate {
+    /// The estimated expected value.
+    pub mean: f64,
+    /// The standard error of the estimate.
+    pub error: f64,
+    /// The confidence interval for the expected value.
+    pub interval: (f64, f64),
+    /// The effective sample size, that is, the number of independent draws
+    /// without variance reduction yielding the same standard error.
+    pub effective_size: f64,
+    /// The number of draws used.
+    pub draws: usize,
+}
+
+/// A Monte Carlo estimator of expected values.
+#[derive(Clone, Copy, Debug)]
+pub struct Integrator {
+    /// The confidence level of the confidence intervals.
+    pub confidence: f64,
+    /// The maximal number of draws.
+    pub draws: usize,
+    /// The relative standard error at which drawing stops early.
+    pub tolerance: Option<f64>,
+}
+
+#[derive(Default)]
+struct Moments {
+    count: usize,
+    x: f64,
+    y: f64,
+    xx: f64,
+    xy: f64,
+    yy: f64,
+}
+
+/// The minimal number of draws taken before checking the tolerance.
+pub const MINIMUM: usize = 100;
+
+impl Estimate {
+    /// Compute the standard error relative to the magnitude of the mean.
+    #[inline]
+    pub fn relative_error(&self) -> f64 {
+        self.error / self.mean.abs()
+    }
+}
+
+impl Integrator {
+    /// Create an integrator taking at most `draws` draws with the confidence
+    /// level of 0.95 and no tolerance.
+    ///
+    /// It should hold that `draws > 1`.
+    #[inline]
+    pub fn new(draws: usize) -> Self {
+        should!(draws > 1);
+        Integrator {
+            confidence: 0.95,
+            draws: draws,
+            tolerance: None,
+        }
+    }
+
+    /// Estimate the expected value of `function` applied to the outcomes of
+    /// `distribution`.
+    pub fn estimate<D, F, S>(&self, distribution: &D, mut function: F, source: &mut S) -> Estimate
+    where
+        D: Sample,
+        F: FnMut(&D::Value) -> f64,
+        S: Source,
+    {
+        self.run(None, || (function(&distribution.sample(source)), 0.0))
+    }
+
+    /// Estimate the expected value of `function` applied to the outcomes of
+    /// `distribution` using `control` as a control variate whose expected
+    /// value is `mean`.
+    ///
+    /// The coefficient of the control variate is estimated from the draws. A
+    /// typical choice of the control variate is the outcome itself with the
+    /// mean given by the `Mean` trait.
+    pub fn estimate_controlled<D, F, G, S>(
+        &self,
+        distribution: &D,
+        mut function: F,
+        mut control: G,
+        mean: f64,
+        source: &mut S,
+    ) -> Estimate
+    where
+        D: Sample,
+        F: FnMut(&D::Value) -> f64,
+        G: FnMut(&D::Value) -> f64,
+        S: Source,
+    {
+        self.run(Some(mean), || {
+            let x = distribution.sample(source);
+            (function(&x), control(&x))
+        })
+    }
+
+    fn run<F>(&self, mean: Option<f64>, mut draw: F) -> Estimate
+    where
+        F: FnMut() -> (f64, f64),
+    {
+        should!(0.0 < self.confidence && self.confidence < 1.0);
+        let mut moments = Moments::default();
+        for _ in 0..self.draws {
+            let (x, y) = draw();
+            moments.push(x, y);
+            if let Some(tolerance) = self.tolerance {
+                if moments.count >= MINIMUM {
+                    let (value, error, _) = moments.summarize(mean);
+                    if error <= tolerance * value.abs() {
+                        break;
+                    }
+                }
+            }
+        }
+        let (value, error, effective_size) = moments.summarize(mean);
+        let delta = if error == 0.0 {
+            0.0
+        } else if effective_size > 1.0 {
+            let p = (1.0 + self.confidence) / 2.0;
+            error * StudentsT::new(effective_size - 1.0, 0.0, 1.0).inverse(p)
+        } else {
+            ::std::f64::INFINITY
+        };
+        Estimate {
+            mean: value,
+            error: error,
+            interval: (value - delta, value + delta),
+            effective_size: effective_size,
+            draws: moments.count,
+        }
+    }
+}
+
+impl Moments {
+    fn push(&mut self, x: f64, y: f64) {
+        self.count += 1;
+        let n = self.count as f64;
+        let (dx, dy) = (x - self.x, y - self.y);
+        self.x += dx / n;
+        self.y += dy / n;
+        self.xx += dx * (x - self.x);
+        self.xy += dx * (y - self.y);
+        self.yy += dy * (y - self.y);
+    }
+
+    // Compute the estimate, its standard error, and the effective sample
+    // size.
+    fn summarize(&self, mean: Option<f64>) -> (f64, f64, f64) {
+        let n = self.count as f64;
+        match mean {
+            Some(mean) if self.yy > 0.0 && n > 2.0 => {
+                let beta = self.xy / self.yy;
+                let residual = (self.xx - beta * self.xy).max(0.0) / (n - 2.0);
+                let variance = self.xx / (n - 1.0);
+                let effective_size = if residual > 0.0 {
+                    n * variance / residual
+                } else {
+                    ::std::f64::INFINITY
+                };
+                (
+                    self.x - beta * (self.y - mean),
+                    (residual / n).sqrt(),
+                    effective_size,
+                )
+            }
+            _ => (self.x, (self.xx / (n - 1.0) / n).sqrt(), n),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    use super::Integrator;
+
+    #[test]
+    fn estimate() {
+        let mut source = source::default();
+        let d = Uniform::new(0.0, 1.0);
+        let estimate = Integrator::new(100000).estimate(&d, |&x| x * x, &mut source);
+        assert_eq!(estimate.draws, 100000);
+        assert_eq!(estimate.effective_size, 100000.0);
+        assert::close(estimate.mean, 1.0 / 3.0, 5e-3);
+        assert::close(estimate.error, (4.0f64 / 45.0 / 100000.0).sqrt(), 1e-5);
+        assert!(estimate.interval.0 < 1.0 / 3.0 && 1.0 / 3.0 < estimate.interval.1);
+        assert::close(
+            estimate.interval.1 - estimate.mean,
+            1.959_963_984_540_054 * estimate.error,
+            1e-6,
+        );
+
+        let d = Bernoulli::new(0.5);
+        let estimate = Integrator::new(10).estimate(&d, |_| 2.0, &mut source);
+        assert_eq!(estimate.mean, 2.0);
+        assert_eq!(estimate.error, 0.0);
+        assert_eq!(estimate.interval, (2.0, 2.0));
+        assert_eq!(estimate.effective_size, 10.0);
+    }
+
+    #[test]
+    fn estimate_controlled() {
+        let mut source = source::default();
+        let d = Uniform::new(0.0, 1.0);
+        let integrator = Integrator::new(10000);
+        let plain = integrator.estimate(&d, |&x| x.exp(), &mut source);
+        let controlled =
+            integrator.estimate_controlled(&d, |&x| x.exp(), |&x| x, d.mean(), &mut source);
+        let expected = 1f64.exp() - 1.0;
+        assert::close(controlled.mean, expected, 2e-3);
+        assert!(controlled.interval.0 < expected && expected < controlled.interval.1);
+        assert!(controlled.error < plain.error / 5.0);
+        assert!(controlled.effective_size > 25.0 * 10000.0);
+    }
+
+    #[test]
+    fn tolerance() {
+        let mut source = source::default();
+        let d = Gaussian::new(10.0, 1.0);
+        let mut integrator = Integrator::new(1000000);
+        integrator.tolerance = Some(1e-3);
+        let estimate = integrator.estimate(&d, |&x| x, &mut source);
+        assert!(estimate.draws < 1000000);
+        assert!(estimate.relative_error() <= 1e-3);
+        assert::close(estimate.mean, 10.0, 0.05);
+    }
+}
@@ -6,6 +6,8 @@ pub use distribution::Continuous;
 pub use distribution::Discrete;
 
 pub use distribution::Entropy;
+pub use distribution::Fit;
+pub use distribution::Gradient;
 pub use distribution::Inverse;
 pub use distribution::Kurtosis;
 pub use distribution::Mean;
@@ -15,20 +17,32 @@ pub use distribution::Sample;
 pub use distribution::Skewness;
 pub use distribution::Variance;
 
+pub use distribution::AnyDistribution;
 pub use distribution::Bernoulli;
 pub use distribution::Beta;
 pub use distribution::Binomial;
 pub use distribution::Categorical;
+pub use distribution::ChiSquared;
+pub use distribution::Dirichlet;
 pub use distribution::Exponential;
+pub use distribution::FisherSnedecor;
 pub use distribution::Gamma;
 pub use distribution::Gaussian;
 pub use distribution::Laplace;
 pub use distribution::Logistic;
 pub use distribution::Lognormal;
+pub use distribution::Multinomial;
+pub use distribution::MultivariateGaussian;
 pub use distribution::Pert;
+pub use distribution::Poisson;
+pub use distribution::StudentsT;
 pub use distribution::Triangular;
 pub use distribution::Uniform;
+pub use distribution::WeightedCategorical;
 
+pub use sampler::Antithetic;
 pub use sampler::Independent;
+pub use sampler::LatinHypercube;
+pub use sampler::Stratified;
 
 pub use source;
@@ -0,0 +1,170 @@
+use qmc::Sequence;
+use source::Source;
+
+/// A Halton sequence.
+///
+/// The bases are the prime numbers in increasing order, and the sequence
+/// starts at the origin. Scrambling is done by random permutations of the
+/// digits drawn independently for each dimension and each digit position,
+/// which preserves the stratification of the sequence.
+#[derive(Clone, Debug)]
+pub struct Halton {
+    bases: Vec<u64>,
+    permutations: Vec<Vec<Vec<u64>>>,
+    index: u64,
+}
+
+impl Halton {
+    /// Create a sequence with `dimension` dimensions.
+    ///
+    /// It should hold that `dimension > 0`.
+    pub fn new(dimension: usize) -> Self {
+        should!(dimension > 0);
+        Halton {
+            bases: primes(dimension),
+            permutations: Vec::new(),
+            index: 0,
+        }
+    }
+
+    /// Create a sequence with `dimension` dimensions scrambled using `source`.
+    ///
+    /// It should hold that `dimension > 0`.
+    pub fn scrambled<S>(dimension: usize, source: &mut S) -> Self
+    where
+        S: Source,
+    {
+        let mut sequence = Halton::new(dimension);
+        sequence.permutations = sequence
+            .bases
+            .iter()
+            .map(|&base| {
+                let depth = (52.0 / (base as f64).log2()).ceil() as usize;
+                (0..depth).map(|_| permutation(base, source)).collect()
+            })
+            .collect();
+        sequence
+    }
+}
+
+impl Iterator for Halton {
+    type Item = Vec<f64>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Vec<f64>> {
+        let mut point = vec![0.0; self.dimension()];
+        self.read(&mut point);
+        Some(point)
+    }
+}
+
+impl Sequence for Halton {
+    #[inline]
+    fn dimension(&self) -> usize {
+        self.bases.len()
+    }
+
+    fn read(&mut self, point: &mut [f64]) {
+        should!(point.len() == self.dimension());
+        for (i, (x, &base)) in point.iter_mut().zip(&self.bases).enumerate() {
+            let (mut n, mut scale) = (self.index, 1.0 / base as f64);
+            *x = 0.0;
+            match self.permutations.get(i) {
+                Some(permutations) => {
+                    for permutation in permutations {
+                        *x += permutation[(n % base) as usize] as f64 * scale;
+                        n /= base;
+                        scale /= base as f64;
+                    }
+                    *x = x.min(1.0 - ::std::f64::EPSILON / 2.0);
+                }
+                _ => {
+                    while n > 0 {
+                        *x += (n % base) as f64 * scale;
+                        n /= base;
+                        scale /= base as f64;
+                    }
+                }
+            }
+        }
+        self.index += 1;
+    }
+}
+
+fn permutation<S>(base: u64, source: &mut S) -> Vec<u64>
+where
+    S: Source,
+{
+    let mut values = (0..base).collect::<Vec<_>>();
+    for i in (1..values.len()).rev() {
+        let j = ((source.read::<f64>() * (i + 1) as f64) as usize).min(i);
+        values.swap(i, j);
+    }
+    values
+}
+
+fn primes(count: usize) -> Vec<u64> {
+    let mut primes = Vec::with_capacity(count);
+    let mut candidate = 2;
+    while primes.len() < count {
+        if primes
+            .iter()
+            .take_while(|&&p| p * p <= candidate)
+            .all(|&p| candidate % p != 0)
+        {
+            primes.push(candidate);
+        }
+        candidate += 1;
+    }
+    primes
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use qmc::{Halton, Sequence};
+
+    #[test]
+    fn new() {
+        let points = Halton::new(3).take(5).collect::<Vec<_>>();
+        let expected = vec![
+            vec![0.0, 0.0, 0.0],
+            vec![0.5, 1.0 / 3.0, 0.2],
+            vec![0.25, 2.0 / 3.0, 0.4],
+            vec![0.75, 1.0 / 9.0, 0.6],
+            vec![0.125, 4.0 / 9.0, 0.8],
+        ];
+        for (point, expected) in points.iter().zip(&expected) {
+            assert::close(point, expected, 1e-15);
+        }
+
+        let mut sequence = Halton::new(10);
+        let mut point = vec![0.0; 10];
+        sequence.read(&mut point);
+        sequence.read(&mut point);
+        assert::close(point[9], 1.0 / 29.0, 1e-15);
+    }
+
+    #[test]
+    fn scrambled() {
+        let mut source = source::default();
+        let points = Halton::scrambled(3, &mut source)
+            .take(81)
+            .collect::<Vec<_>>();
+        assert!(points[0].iter().all(|&x| 0.0 < x && x < 1.0));
+        let mut cells = points
+            .iter()
+            .map(|point| (point[1] * 81.0) as usize)
+            .collect::<Vec<_>>();
+        cells.sort();
+        assert_eq!(cells, (0..81).collect::<Vec<_>>());
+        let mut cells = points
+            .iter()
+            .take(25)
+            .map(|point| (point[2] * 25.0) as usize)
+            .collect::<Vec<_>>();
+        cells.sort();
+        assert_eq!(cells, (0..25).collect::<Vec<_>>());
+    }
+}
@@ -0,0 +1,77 @@
+//! Quasi-Monte Carlo sequences.
+//!
+//! Unlike sources of randomness, which produce streams of independent numbers,
+//! low-discrepancy sequences produce points in the unit hypercube that are
+//! spread evenly across all dimensions at once. The points can be mapped to
+//! the outcomes of distributions via the inverse of the distribution function
+//! using `Quasi`. Scrambled sequences are randomized while retaining their
+//! uniformity, so that independent replicates give error estimates.
+
+use sampler::Marginals;
+
+/// A low-discrepancy sequence of points in the unit hypercube.
+pub trait Sequence {
+    /// Return the number of dimensions.
+    fn dimension(&self) -> usize;
+
+    /// Write the next point into `point`.
+    ///
+    /// The length of `point` should be equal to the number of dimensions.
+    fn read(&mut self, point: &mut [f64]);
+}
+
+/// A means of drawing a sequence of quasi-random samples.
+///
+/// The first item is a collection of marginal distributions, and the second
+/// is a low-discrepancy sequence with as many dimensions as there are
+/// marginals.
+pub struct Quasi<M, Q>(pub M, pub Q);
+
+impl<'a, M, Q> Iterator for Quasi<&'a M, &'a mut Q>
+where
+    M: Marginals + ?Sized,
+    Q: Sequence,
+{
+    type Item = M::Value;
+
+    #[inline]
+    fn next(&mut self) -> Option<M::Value> {
+        should!(self.0.dimension() == self.1.dimension());
+        let mut point = vec![0.0; self.1.dimension()];
+        self.1.read(&mut point);
+        Some(self.0.inverse(&point))
+    }
+}
+
+mod halton;
+mod sobol;
+
+pub use self::halton::Halton;
+pub use self::sobol::Sobol;
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+    use qmc::{Quasi, Sobol};
+
+    #[test]
+    fn quasi() {
+        let mut source = source::default();
+        let marginals = (Uniform::new(0.0, 2.0), Gaussian::new(1.0, 2.0));
+        let n = 1 << 12;
+        let estimates = (0..8)
+            .map(|_| {
+                let mut sequence = Sobol::scrambled(2, &mut source);
+                Quasi(&marginals, &mut sequence)
+                    .take(n)
+                    .fold(0.0, |sum, (x, y)| sum + x * y)
+                    / n as f64
+            })
+            .collect::<Vec<_>>();
+        for &estimate in &estimates {
+            assert::close(estimate, 1.0, 1e-2);
+        }
+        assert!(estimates.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+}
@@ -0,0 +1,216 @@
+use qmc::Sequence;
+use source::Source;
+
+/// A Sobol sequence.
+///
+/// The direction numbers are the ones of Joe and Kuo, which are included for
+/// up to 21 dimensions. The sequence starts at the origin and enumerates the
+/// points in the Gray-code order. Scrambling is done by a random linear matrix
+/// scrambling followed by a random digital shift, which preserves the net
+/// properties of the sequence.
+#[derive(Clone, Debug)]
+pub struct Sobol {
+    directions: Vec<Vec<u64>>,
+    state: Vec<u64>,
+    index: u64,
+}
+
+const BITS: usize = 52;
+
+// The degree, the coefficients, and the initial direction numbers of the
+// primitive polynomials of Joe and Kuo for the dimensions starting from the
+// second one.
+const POLYNOMIALS: [(usize, u64, &[u64]); 20] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+    (5, 11, &[1, 1, 5, 1, 1]),
+    (5, 13, &[1, 1, 1, 3, 11]),
+    (5, 14, &[1, 3, 5, 5, 31]),
+    (6, 1, &[1, 3, 3, 9, 7, 49]),
+    (6, 13, &[1, 1, 1, 15, 21, 21]),
+    (6, 16, &[1, 3, 1, 13, 27, 49]),
+    (6, 19, &[1, 1, 1, 15, 7, 5]),
+    (6, 22, &[1, 3, 1, 15, 13, 25]),
+    (6, 25, &[1, 1, 5, 5, 19, 61]),
+    (7, 1, &[1, 3, 7, 11, 23, 15, 103]),
+    (7, 4, &[1, 3, 7, 13, 13, 15, 69]),
+];
+
+impl Sobol {
+    /// Create a sequence with `dimension` dimensions.
+    ///
+    /// It should hold that `0 < dimension <= 21`.
+    pub fn new(dimension: usize) -> Self {
+        should!(0 < dimension && dimension <= POLYNOMIALS.len() + 1);
+        Sobol {
+            directions: (0..dimension).map(directions).collect(),
+            state: vec![0; dimension],
+            index: 0,
+        }
+    }
+
+    /// Create a sequence with `dimension` dimensions scrambled using `source`.
+    ///
+    /// It should hold that `0 < dimension <= 21`.
+    pub fn scrambled<S>(dimension: usize, source: &mut S) -> Self
+    where
+        S: Source,
+    {
+        let mask = (1 << BITS) - 1;
+        let mut sequence = Sobol::new(dimension);
+        for (directions, state) in sequence.directions.iter_mut().zip(&mut sequence.state) {
+            let rows = (0..BITS)
+                .map(|k| {
+                    let bit = 1 << (BITS - 1 - k);
+                    (source.read::<u64>() & mask & !((bit << 1) - 1)) | bit
+                })
+                .collect::<Vec<u64>>();
+            for v in directions.iter_mut() {
+                *v = rows.iter().fold(0, |w, &row| {
+                    (w << 1) | u64::from((row & *v).count_ones() & 1)
+                });
+            }
+            *state = source.read::<u64>() & mask;
+        }
+        sequence
+    }
+}
+
+impl Iterator for Sobol {
+    type Item = Vec<f64>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Vec<f64>> {
+        let mut point = vec![0.0; self.dimension()];
+        self.read(&mut point);
+        Some(point)
+    }
+}
+
+impl Sequence for Sobol {
+    #[inline]
+    fn dimension(&self) -> usize {
+        self.state.len()
+    }
+
+    fn read(&mut self, point: &mut [f64]) {
+        should!(point.len() == self.dimension());
+        let scale = (1u64 << BITS) as f64;
+        for (x, &state) in point.iter_mut().zip(&self.state) {
+            *x = state as f64 / scale;
+        }
+        let c = (!self.index).trailing_zeros() as usize;
+        should!(c < BITS);
+        for (state, directions) in self.state.iter_mut().zip(&self.directions) {
+            *state ^= directions[c];
+        }
+        self.index += 1;
+    }
+}
+
+fn directions(dimension: usize) -> Vec<u64> {
+    if dimension == 0 {
+        return (0..BITS).map(|j| 1 << (BITS - 1 - j)).collect();
+    }
+    let (s, a, m) = POLYNOMIALS[dimension - 1];
+    let mut v = m
+        .iter()
+        .enumerate()
+        .map(|(j, &m)| m << (BITS - 1 - j))
+        .collect::<Vec<_>>();
+    for j in s..BITS {
+        let mut value = v[j - s] ^ (v[j - s] >> s);
+        for k in 1..s {
+            if (a >> (s - 1 - k)) & 1 == 1 {
+                value ^= v[j - k];
+            }
+        }
+        v.push(value);
+    }
+    v
+}
+
+#[cfg(test)]
+mod tests {
+    use prelude::*;
+    use qmc::{Sequence, Sobol};
+
+    #[test]
+    fn new() {
+        let points = Sobol::new(3).take(8).collect::<Vec<_>>();
+        assert_eq!(
+            points,
+            vec![
+                vec![0.0, 0.0, 0.0],
+                vec![0.5, 0.5, 0.5],
+                vec![0.75, 0.25, 0.25],
+                vec![0.25, 0.75, 0.75],
+                vec![0.375, 0.375, 0.625],
+                vec![0.875, 0.875, 0.125],
+                vec![0.625, 0.125, 0.875],
+                vec![0.125, 0.625, 0.375],
+            ]
+        );
+    }
+
+    #[test]
+    fn net() {
+        let m = 10;
+        let points = Sobol::new(21).take(1 << m).collect::<Vec<_>>();
+        for i in 0..21 {
+            let mut cells = points
+                .iter()
+                .map(|point| (point[i] * (1 << m) as f64) as usize)
+                .collect::<Vec<_>>();
+            cells.sort();
+            assert_eq!(cells, (0..(1 << m)).collect::<Vec<_>>());
+        }
+        for a in 0..(m + 1) {
+            let mut cells = points
+                .iter()
+                .map(|point| {
+                    let x = (point[0] * (1 << a) as f64) as usize;
+                    let y = (point[1] * (1 << (m - a)) as f64) as usize;
+                    (x, y)
+                })
+                .collect::<Vec<_>>();
+            cells.sort();
+            cells.dedup();
+            assert_eq!(cells.len(), 1 << m);
+        }
+    }
+
+    #[test]
+    fn scrambled() {
+        let mut source = source::default();
+        let m = 8;
+        let mut sequence = Sobol::scrambled(5, &mut source);
+        assert_eq!(sequence.dimension(), 5);
+        let mut point = vec![0.0; 5];
+        sequence.read(&mut point);
+        assert!(point.iter().all(|&x| 0.0 < x && x < 1.0));
+        let points = Some(point)
+            .into_iter()
+            .chain(sequence.take((1 << m) - 1))
+            .collect::<Vec<_>>();
+        for i in 0..5 {
+            let mut cells = points
+                .iter()
+                .map(|point| (point[i] * (1 << m) as f64) as usize)
+                .collect::<Vec<_>>();
+            cells.sort();
+            assert_eq!(cells, (0..(1 << m)).collect::<Vec<_>>());
+        }
+        let other = Sobol::scrambled(5, &mut source)
+            .take(1 << m)
+            .collect::<Vec<_>>();
+        assert!(points != other);
+    }
+}
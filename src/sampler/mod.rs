@@ -1,11 +1,95 @@
 //! Samplers of random numbers.
 
-use distribution::Sample;
+use distribution::{Inverse, Sample};
 use source::Source;
 
+/// A means of drawing a sequence of antithetic pairs of samples.
+///
+/// Each pair consists of the inverse of the distribution function evaluated
+/// at `u` and at `1 - u` where `u` is drawn uniformly.
+pub struct Antithetic<D, S>(pub D, pub S);
+
 /// A means of drawing a sequence of independent samples.
 pub struct Independent<D, S>(pub D, pub S);
 
+/// A means of drawing a sequence of Latin hypercube samples.
+///
+/// The samples come in blocks of a given size. Within each block, the range of
+/// the distribution function of each marginal is split into as many strata of
+/// equal probability as there are samples in the block, each stratum is hit
+/// exactly once, and the strata of different marginals are paired at random.
+pub struct LatinHypercube<M, S> {
+    marginals: M,
+    source: S,
+    size: usize,
+    strata: Vec<Vec<usize>>,
+    p: Vec<f64>,
+    index: usize,
+}
+
+/// Marginal distributions capable of inverting the distribution function.
+///
+/// The trait is implemented for slices and tuples of distributions.
+pub trait Marginals {
+    /// The type of outcomes.
+    type Value;
+
+    /// Return the number of marginals.
+    fn dimension(&self) -> usize;
+
+    /// Compute the inverse of the distribution function of each marginal at
+    /// the corresponding element of `p`.
+    fn inverse(&self, p: &[f64]) -> Self::Value;
+}
+
+/// A means of drawing a sequence of stratified samples.
+///
+/// The range of the distribution function is split into a number of strata of
+/// equal probability, and the samples come in blocks of as many samples as
+/// there are strata. Each block hits each stratum exactly once, and the strata
+/// are visited in random order, so that a partial block is not biased.
+pub struct Stratified<D, S> {
+    distribution: D,
+    source: S,
+    strata: Vec<usize>,
+    index: usize,
+}
+
+macro_rules! implement(
+    ($($kind:ident $index:tt),*) => (
+        impl<$($kind),*> Marginals for ($($kind,)*)
+        where
+            $($kind: Inverse,)*
+        {
+            type Value = ($($kind::Value,)*);
+
+            #[inline]
+            fn dimension(&self) -> usize {
+                [$($index),*].len()
+            }
+
+            #[inline]
+            fn inverse(&self, p: &[f64]) -> Self::Value {
+                ($(self.$index.inverse(p[$index]),)*)
+            }
+        }
+    );
+);
+
+impl<'a, T, D, S> Iterator for Antithetic<&'a D, &'a mut S>
+where
+    D: Inverse<Value = T>,
+    S: Source,
+{
+    type Item = (T, T);
+
+    #[inline]
+    fn next(&mut self) -> Option<(T, T)> {
+        let u = open(self.1.read::<f64>());
+        Some((self.0.inverse(u), self.0.inverse(1.0 - u)))
+    }
+}
+
 impl<'a, T, D, S> Iterator for Independent<&'a D, &'a mut S>
 where
     D: Sample<Value = T>,
@@ -18,3 +102,231 @@ where
         Some(self.0.sample(self.1))
     }
 }
+
+impl<M, S> LatinHypercube<M, S> {
+    /// Create a sampler with blocks of `size` samples.
+    ///
+    /// It should hold that `size > 0`.
+    #[inline]
+    pub fn new(marginals: M, source: S, size: usize) -> Self {
+        should!(size > 0);
+        LatinHypercube {
+            marginals: marginals,
+            source: source,
+            size: size,
+            strata: Vec::new(),
+            p: Vec::new(),
+            index: 0,
+        }
+    }
+}
+
+impl<'a, M, S> Iterator for LatinHypercube<&'a M, &'a mut S>
+where
+    M: Marginals + ?Sized,
+    S: Source,
+{
+    type Item = M::Value;
+
+    fn next(&mut self) -> Option<M::Value> {
+        let (m, n) = (self.marginals.dimension(), self.size);
+        if self.index == 0 {
+            if self.strata.is_empty() {
+                self.strata = vec![(0..n).collect(); m];
+                self.p = vec![0.0; m];
+            }
+            for strata in &mut self.strata {
+                shuffle(strata, self.source);
+            }
+        }
+        for (p, strata) in self.p.iter_mut().zip(&self.strata) {
+            *p = open((strata[self.index] as f64 + self.source.read::<f64>()) / n as f64);
+        }
+        self.index = (self.index + 1) % n;
+        Some(self.marginals.inverse(&self.p))
+    }
+}
+
+impl<D> Marginals for [D]
+where
+    D: Inverse,
+{
+    type Value = Vec<D::Value>;
+
+    #[inline]
+    fn dimension(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn inverse(&self, p: &[f64]) -> Self::Value {
+        self.iter().zip(p).map(|(d, &p)| d.inverse(p)).collect()
+    }
+}
+
+impl<D, S> Stratified<D, S> {
+    /// Create a sampler with `strata` strata.
+    ///
+    /// It should hold that `strata > 0`.
+    #[inline]
+    pub fn new(distribution: D, source: S, strata: usize) -> Self {
+        should!(strata > 0);
+        Stratified {
+            distribution: distribution,
+            source: source,
+            strata: (0..strata).collect(),
+            index: 0,
+        }
+    }
+}
+
+impl<'a, T, D, S> Iterator for Stratified<&'a D, &'a mut S>
+where
+    D: Inverse<Value = T>,
+    S: Source,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        let n = self.strata.len();
+        if self.index == 0 {
+            shuffle(&mut self.strata, self.source);
+        }
+        let p = open((self.strata[self.index] as f64 + self.source.read::<f64>()) / n as f64);
+        self.index = (self.index + 1) % n;
+        Some(self.distribution.inverse(p))
+    }
+}
+
+implement!(A 0);
+implement!(A 0, B 1);
+implement!(A 0, B 1, C 2);
+implement!(A 0, B 1, C 2, D 3);
+
+// Restrict `p` to the open interval (0, 1), so that the inverse of the
+// distribution function is finite. The bounds are symmetric, so that `1 - p` is
+// restricted as well.
+#[inline]
+fn open(p: f64) -> f64 {
+    const LIMIT: f64 = ::std::f64::EPSILON / 2.0;
+    p.clamp(LIMIT, 1.0 - LIMIT)
+}
+
+fn shuffle<S>(values: &mut [usize], source: &mut S)
+where
+    S: Source,
+{
+    for i in (1..values.len()).rev() {
+        let j = ((source.read::<f64>() * (i + 1) as f64) as usize).min(i);
+        values.swap(i, j);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert;
+    use prelude::*;
+
+    #[test]
+    fn antithetic() {
+        let mut source = source::default();
+        let d = Uniform::new(0.0, 1.0);
+        for (x, y) in Antithetic(&d, &mut source).take(100) {
+            assert::close(x + y, 1.0, 1e-15);
+        }
+
+        let d = Gaussian::new(1.0, 2.0);
+        let n = 1000;
+        let sum = Antithetic(&d, &mut source)
+            .take(n)
+            .fold(0.0, |sum, (x, y)| sum + x + y);
+        assert::close(sum / (2 * n) as f64, 1.0, 1e-12);
+    }
+
+    #[test]
+    fn latin_hypercube() {
+        let mut source = source::default();
+        let marginals = (Uniform::new(0.0, 1.0), Uniform::new(0.0, 10.0));
+        let n = 20;
+        let samples = LatinHypercube::new(&marginals, &mut source, n)
+            .take(2 * n)
+            .collect::<Vec<_>>();
+        for block in samples.chunks(n) {
+            let mut x = block
+                .iter()
+                .map(|&(x, _)| (x * 20.0) as usize)
+                .collect::<Vec<_>>();
+            let mut y = block
+                .iter()
+                .map(|&(_, y)| (y * 2.0) as usize)
+                .collect::<Vec<_>>();
+            x.sort();
+            y.sort();
+            assert_eq!(x, (0..n).collect::<Vec<_>>());
+            assert_eq!(y, (0..n).collect::<Vec<_>>());
+        }
+
+        let marginals = vec![Bernoulli::new(0.5); 3];
+        let samples = LatinHypercube::new(&marginals[..], &mut source, 4)
+            .take(4)
+            .collect::<Vec<_>>();
+        for k in 0..3 {
+            assert_eq!(samples.iter().map(|x| x[k]).sum::<u8>(), 2);
+        }
+    }
+
+    #[test]
+    fn open() {
+        use source::Source;
+
+        struct Zero;
+
+        impl Source for Zero {
+            fn read_u64(&mut self) -> u64 {
+                0
+            }
+
+            fn read_f64(&mut self) -> f64 {
+                0.0
+            }
+        }
+
+        let d = Gaussian::new(0.0, 1.0);
+        let (x, y) = Antithetic(&d, &mut Zero).next().unwrap();
+        assert!(x.is_finite() && y.is_finite());
+        assert!(Stratified::new(&d, &mut Zero, 3)
+            .take(6)
+            .all(|x| x.is_finite()));
+        let marginals = [d, d];
+        assert!(LatinHypercube::new(&marginals[..], &mut Zero, 3)
+            .take(6)
+            .all(|x| x.iter().all(|x| x.is_finite())));
+    }
+
+    #[test]
+    fn stratified() {
+        let mut source = source::default();
+        let d = Uniform::new(0.0, 4.0);
+        let samples = Stratified::new(&d, &mut source, 4)
+            .take(8)
+            .collect::<Vec<_>>();
+        for block in samples.chunks(4) {
+            let mut strata = block.iter().map(|&x| x as usize).collect::<Vec<_>>();
+            strata.sort();
+            assert_eq!(strata, vec![0, 1, 2, 3]);
+        }
+
+        let d = Uniform::new(0.0, 1.0);
+        let mean = Stratified::new(&d, &mut source, 1000)
+            .take(100)
+            .sum::<f64>()
+            / 100.0;
+        assert::close(mean, 0.5, 0.1);
+
+        let d = Exponential::new(1.0);
+        let n = 1000;
+        let mean = Stratified::new(&d, &mut source, n).take(n).sum::<f64>() / n as f64;
+        assert::close(mean, 1.0, 1e-2);
+    }
+}
@@ -1,3 +1,21 @@
 //! Sources of randomness.
 
 pub use random::*;
+
+/// A source backed by a generator of the [`rand`] crate.
+///
+/// [`rand`]: https://crates.io/crates/rand
+#[cfg(feature = "rand")]
+#[derive(Clone, Debug)]
+pub struct Rand<T>(pub T);
+
+#[cfg(feature = "rand")]
+impl<T> Source for Rand<T>
+where
+    T: ::rand::RngCore,
+{
+    #[inline(always)]
+    fn read_u64(&mut self) -> u64 {
+        self.0.next_u64()
+    }
+}